@@ -0,0 +1,292 @@
+//! Provides declarative sets of accessed keys
+//! which can be validated for conflicts and claimed from [`Many`] collections at once.
+
+use alloc_crate::vec::Vec;
+use core::fmt;
+
+use crate::{Availability, Many, ManyAvailability, MoveError, MoveResult};
+
+/// Set of keys which are going to be read or written.
+///
+/// Systems can declare their accesses up front, check them for [conflicts](AccessSet::conflicts_with)
+/// with accesses of other systems and [claim](AccessSet::claim) all the references at once.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{access::AccessSet, RefKind};
+///
+/// let mut numbers = [0, 1, 2, 3];
+/// let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+///
+/// let physics = AccessSet::new().read(0).write(1);
+/// let audio = AccessSet::new().read(0).write(2).write(3);
+/// let render = AccessSet::new().read(1);
+///
+/// assert_eq!(physics.conflicts_with(&audio), None);
+/// assert_eq!(physics.conflicts_with(&render), Some(&1));
+///
+/// let claim = physics.claim(&mut many).unwrap();
+/// assert_eq!(claim.reads, [Some(&0)]);
+/// assert_eq!(claim.writes, [Some(&mut 1)]);
+///
+/// let error = render.claim(&mut many).unwrap_err();
+/// assert_eq!(error.key, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccessSet<Key> {
+    reads: Vec<Key>,
+    writes: Vec<Key>,
+}
+
+impl<Key> AccessSet<Key> {
+    /// Creates new empty access set.
+    pub const fn new() -> Self {
+        Self {
+            reads: Vec::new(),
+            writes: Vec::new(),
+        }
+    }
+
+    /// Declares that the key is going to be read.
+    pub fn read(mut self, key: Key) -> Self {
+        self.reads.push(key);
+        self
+    }
+
+    /// Declares that the key is going to be written.
+    pub fn write(mut self, key: Key) -> Self {
+        self.writes.push(key);
+        self
+    }
+
+    /// Returns keys which are going to be read, in the order of declaration.
+    pub fn reads(&self) -> &[Key] {
+        &self.reads
+    }
+
+    /// Returns keys which are going to be written, in the order of declaration.
+    pub fn writes(&self) -> &[Key] {
+        &self.writes
+    }
+}
+
+impl<Key> AccessSet<Key>
+where
+    Key: PartialEq,
+{
+    /// Returns the first key which access of this set conflicts with access of the other set.
+    ///
+    /// Sets conflict if some key is written by one of them and read or written by another.
+    pub fn conflicts_with<'s>(&'s self, other: &AccessSet<Key>) -> Option<&'s Key> {
+        let writes = self
+            .writes
+            .iter()
+            .find(|key| other.reads.contains(key) || other.writes.contains(key));
+        writes.or_else(|| self.reads.iter().find(|key| other.writes.contains(key)))
+    }
+
+    /// Returns the first key which is declared more than once with conflicting access inside this set.
+    ///
+    /// Key conflicts with itself if it is written more than once or both read and written.
+    pub fn self_conflict(&self) -> Option<&Key> {
+        self.writes.iter().enumerate().find_map(|(index, key)| {
+            let conflicts = self.writes[..index].contains(key) || self.reads.contains(key);
+            conflicts.then_some(key)
+        })
+    }
+}
+
+impl<Key> AccessSet<Key>
+where
+    Key: PartialEq + Clone,
+{
+    /// Moves all the declared references out of the collection at once, or none of them.
+    ///
+    /// Conflicts inside this set and [availability](ManyAvailability) of every key
+    /// are checked before any reference is moved out,
+    /// so if some key is missing from the collection or was already claimed elsewhere,
+    /// the collection is left untouched and an error with the offending key is returned.
+    /// Missing keys are reported as [`MoveError::NotFound`].
+    /// Written keys are moved before the read ones.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{access::AccessSet, Many, MoveError, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1, 2];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    /// set.try_move_ref(2).unwrap();
+    ///
+    /// let plan = AccessSet::new().write(0).write(2);
+    /// let error = plan.claim(&mut set).unwrap_err();
+    /// assert_eq!((error.key, error.error), (2, MoveError::BorrowedImmutably));
+    ///
+    /// let plan = AccessSet::new().write(0).read(5);
+    /// let error = plan.claim(&mut set).unwrap_err();
+    /// assert_eq!((error.key, error.error), (5, MoveError::NotFound));
+    ///
+    /// // Nothing was moved out by the failed claims
+    /// assert_eq!(set.try_move_mut(0), Ok(Some(&mut 0)));
+    /// ```
+    pub fn claim<'a, M>(&self, many: &mut M) -> Result<Claim<M::Ref, M::Mut>, ClaimError<Key>>
+    where
+        M: ?Sized + Many<'a, Key> + ManyAvailability<Key>,
+    {
+        self.check(many)?;
+
+        let writes = self
+            .writes
+            .iter()
+            .map(|key| {
+                many.try_move_mut(key.clone())
+                    .map_err(|error| ClaimError::new(key, error))
+            })
+            .collect::<Result<_, _>>()?;
+        let reads = self
+            .reads
+            .iter()
+            .map(|key| {
+                many.try_move_ref(key.clone())
+                    .map_err(|error| ClaimError::new(key, error))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Claim { reads, writes })
+    }
+
+    /// Checks that all the declared references can be moved out of the collection.
+    pub(crate) fn check<M>(&self, many: &M) -> Result<(), ClaimError<Key>>
+    where
+        M: ?Sized + ManyAvailability<Key>,
+    {
+        if let Some(key) = self.self_conflict() {
+            return Err(ClaimError::new(key, MoveError::BorrowedMutably));
+        }
+        for key in &self.writes {
+            check_write(many, key).map_err(|error| ClaimError::new(key, error))?;
+        }
+        for key in &self.reads {
+            check_read(many, key).map_err(|error| ClaimError::new(key, error))?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that a mutable reference can be moved out of the collection by the key.
+pub(crate) fn check_write<M, Key>(many: &M, key: &Key) -> MoveResult<()>
+where
+    M: ?Sized + ManyAvailability<Key>,
+{
+    match many.availability(key) {
+        Some(Availability::Mut) => Ok(()),
+        Some(Availability::Ref) => Err(MoveError::BorrowedImmutably),
+        Some(Availability::Moved) => Err(MoveError::BorrowedMutably),
+        None => Err(MoveError::NotFound),
+    }
+}
+
+/// Checks that an immutable reference can be moved out of the collection by the key.
+pub(crate) fn check_read<M, Key>(many: &M, key: &Key) -> MoveResult<()>
+where
+    M: ?Sized + ManyAvailability<Key>,
+{
+    match many.availability(key) {
+        Some(Availability::Mut | Availability::Ref) => Ok(()),
+        Some(Availability::Moved) => Err(MoveError::BorrowedMutably),
+        None => Err(MoveError::NotFound),
+    }
+}
+
+impl<Key> Default for AccessSet<Key> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits systems into batches which can run at the same time, preserving the order of conflicting systems.
+///
+/// Each batch contains indices of the systems which do not [conflict](AccessSet::conflicts_with) with each other.
+/// A system is placed into the batch which follows the last batch of the previous systems it conflicts with,
+/// so conflicting systems are run in the order of declaration.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::access::{schedule, AccessSet};
+///
+/// let systems = [
+///     AccessSet::new().read(0).write(1),
+///     AccessSet::new().write(0),
+///     AccessSet::new().read(2).write(3),
+///     AccessSet::new().read(1),
+/// ];
+/// assert_eq!(schedule(&systems), [vec![0, 2], vec![1, 3]]);
+/// ```
+pub fn schedule<Key>(systems: &[AccessSet<Key>]) -> Vec<Vec<usize>>
+where
+    Key: PartialEq,
+{
+    let mut batches: Vec<Vec<usize>> = Vec::new();
+    let mut placed = Vec::with_capacity(systems.len());
+    for (index, system) in systems.iter().enumerate() {
+        let batch = systems[..index]
+            .iter()
+            .zip(&placed)
+            .filter(|(previous, _)| previous.conflicts_with(system).is_some())
+            .map(|(_, &batch)| batch + 1)
+            .max()
+            .unwrap_or(0);
+        match batches.get_mut(batch) {
+            Some(indices) => indices.push(index),
+            None => batches.push(Vec::from([index])),
+        }
+        placed.push(batch);
+    }
+    batches
+}
+
+/// References claimed by the [`AccessSet`], in the order of declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Claim<Ref, Mut> {
+    /// Immutable references of the read keys.
+    pub reads: Vec<Ref>,
+    /// Mutable references of the written keys.
+    pub writes: Vec<Mut>,
+}
+
+/// Error which can occur when claiming references by the [`AccessSet`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ClaimError<Key> {
+    /// Key which reference could not be moved out.
+    pub key: Key,
+    /// The reason why the reference could not be moved out.
+    pub error: MoveError,
+}
+
+impl<Key> ClaimError<Key>
+where
+    Key: Clone,
+{
+    pub(crate) fn new(key: &Key, error: MoveError) -> Self {
+        let key = key.clone();
+        Self { key, error }
+    }
+}
+
+impl<Key> fmt::Display for ClaimError<Key>
+where
+    Key: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to claim key {:?}: {}", self.key, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<Key> std_crate::error::Error for ClaimError<Key> where Key: fmt::Debug {}
@@ -0,0 +1,108 @@
+use alloc_crate::collections::BTreeMap;
+
+use crate::{Many, MoveResult};
+
+/// Wrapper of the collection which resolves alternate keys (aliases) into their target keys.
+///
+/// Moves through any alias are performed by the target key,
+/// so all the aliases of the entry share its availability.
+/// This is useful for migrations, when the same entry is known by both old and new names.
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use ref_kind::{Aliased, Many, MoveError, RefKind};
+///
+/// let mut volume = 50;
+/// let many = BTreeMap::from([("audio.volume", Some(RefKind::from(&mut volume)))]);
+/// let mut many = Aliased::new(many);
+/// many.alias("volume", "audio.volume");
+///
+/// assert_eq!(many.try_move_mut("volume"), Ok(Some(&mut 50)));
+/// assert_eq!(many.try_move_ref("audio.volume"), Err(MoveError::BorrowedMutably));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Default)]
+pub struct Aliased<M, Key> {
+    many: M,
+    aliases: BTreeMap<Key, Key>,
+}
+
+impl<M, Key> Aliased<M, Key> {
+    /// Creates new wrapper of the collection with no aliases.
+    pub fn new(many: M) -> Self {
+        let aliases = BTreeMap::new();
+        Self { many, aliases }
+    }
+
+    /// Returns an immutable reference to the underlying collection.
+    pub fn get_ref(&self) -> &M {
+        &self.many
+    }
+
+    /// Returns the underlying collection, consuming the `self` value.
+    pub fn into_inner(self) -> M {
+        self.many
+    }
+}
+
+impl<M, Key> Aliased<M, Key>
+where
+    Key: Ord + Clone,
+{
+    /// Registers the alias of the target key, returning the previous target of the alias.
+    ///
+    /// If the target is an alias itself, the alias is registered for its target,
+    /// and existing aliases which targeted the new alias are re-pointed to its target,
+    /// so aliases are always resolved in one step.
+    /// Aliasing the key to itself (directly or through other aliases) removes the alias.
+    pub fn alias(&mut self, alias: Key, target: Key) -> Option<Key> {
+        let target = self.resolve(target);
+        if target == alias {
+            return self.aliases.remove(&alias);
+        }
+        for previous in self.aliases.values_mut() {
+            if *previous == alias {
+                *previous = target.clone();
+            }
+        }
+        self.aliases.insert(alias, target)
+    }
+
+    /// Removes the alias, returning its target.
+    pub fn unalias(&mut self, alias: &Key) -> Option<Key> {
+        self.aliases.remove(alias)
+    }
+
+    /// Resolves the key into its target if the key is an alias, or returns the key itself.
+    pub fn resolve(&self, key: Key) -> Key {
+        match self.aliases.get(&key) {
+            Some(target) => target.clone(),
+            None => key,
+        }
+    }
+}
+
+/// Implementation of [`Many`] trait for [`Aliased`] which moves references out by the target keys.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, M, Key> Many<'a, Key> for Aliased<M, Key>
+where
+    M: Many<'a, Key>,
+    Key: Ord + Clone,
+{
+    type Ref = M::Ref;
+
+    fn try_move_ref(&mut self, key: Key) -> MoveResult<Self::Ref> {
+        let key = self.resolve(key);
+        self.many.try_move_ref(key)
+    }
+
+    type Mut = M::Mut;
+
+    fn try_move_mut(&mut self, key: Key) -> MoveResult<Self::Mut> {
+        let key = self.resolve(key);
+        self.many.try_move_mut(key)
+    }
+}
@@ -3,34 +3,24 @@ use alloc_crate::{
     vec::Vec,
 };
 
-use crate::{Many, Result};
+use crate::{Availability, Many, ManyAvailability, ManyIndex, MoveResult};
 
 /// Implementation of [`Many`] trait for [`Vec`].
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
-impl<'a, T> Many<'a, usize> for Vec<T>
+impl<'a, T, I> Many<'a, I> for Vec<T>
 where
-    T: Many<'a, usize>,
+    I: ManyIndex<'a, T>,
 {
-    type Ref = Option<T::Ref>;
+    type Ref = I::Ref;
 
-    fn try_move_ref(&mut self, key: usize) -> Result<Self::Ref> {
-        let item = match self.get_mut(key) {
-            Some(item) => item,
-            None => return Ok(None),
-        };
-        let shared = item.try_move_ref(key)?;
-        Ok(Some(shared))
+    fn try_move_ref(&mut self, key: I) -> MoveResult<Self::Ref> {
+        key.try_move_ref(self)
     }
 
-    type Mut = Option<T::Mut>;
+    type Mut = I::Mut;
 
-    fn try_move_mut(&mut self, key: usize) -> Result<Self::Mut> {
-        let item = match self.get_mut(key) {
-            Some(item) => item,
-            None => return Ok(None),
-        };
-        let unique = item.try_move_mut(key)?;
-        Ok(Some(unique))
+    fn try_move_mut(&mut self, key: I) -> MoveResult<Self::Mut> {
+        key.try_move_mut(self)
     }
 }
 
@@ -42,7 +32,7 @@ where
 {
     type Ref = Option<T::Ref>;
 
-    fn try_move_ref(&mut self, key: usize) -> Result<Self::Ref> {
+    fn try_move_ref(&mut self, key: usize) -> MoveResult<Self::Ref> {
         let item = match self.get_mut(key) {
             Some(item) => item,
             None => return Ok(None),
@@ -53,7 +43,7 @@ where
 
     type Mut = Option<T::Mut>;
 
-    fn try_move_mut(&mut self, key: usize) -> Result<Self::Mut> {
+    fn try_move_mut(&mut self, key: usize) -> MoveResult<Self::Mut> {
         let item = match self.get_mut(key) {
             Some(item) => item,
             None => return Ok(None),
@@ -72,7 +62,7 @@ where
 {
     type Ref = Option<V::Ref>;
 
-    fn try_move_ref(&mut self, key: K) -> Result<Self::Ref> {
+    fn try_move_ref(&mut self, key: K) -> MoveResult<Self::Ref> {
         let item = match self.get_mut(&key) {
             Some(item) => item,
             None => return Ok(None),
@@ -83,7 +73,7 @@ where
 
     type Mut = Option<V::Mut>;
 
-    fn try_move_mut(&mut self, key: K) -> Result<Self::Mut> {
+    fn try_move_mut(&mut self, key: K) -> MoveResult<Self::Mut> {
         let item = match self.get_mut(&key) {
             Some(item) => item,
             None => return Ok(None),
@@ -92,3 +82,37 @@ where
         Ok(Some(unique))
     }
 }
+
+/// Implementation of [`ManyAvailability`] trait for [`Vec`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<V> ManyAvailability<usize> for Vec<V>
+where
+    V: ManyAvailability<usize>,
+{
+    fn availability(&self, key: &usize) -> Option<Availability> {
+        self.as_slice().availability(key)
+    }
+}
+
+/// Implementation of [`ManyAvailability`] trait for [`VecDeque`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<V> ManyAvailability<usize> for VecDeque<V>
+where
+    V: ManyAvailability<usize>,
+{
+    fn availability(&self, key: &usize) -> Option<Availability> {
+        self.get(*key)?.availability(key)
+    }
+}
+
+/// Implementation of [`ManyAvailability`] trait for [`BTreeMap`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<K, V> ManyAvailability<K> for BTreeMap<K, V>
+where
+    K: Ord,
+    V: ManyAvailability<K>,
+{
+    fn availability(&self, key: &K) -> Option<Availability> {
+        self.get(key)?.availability(key)
+    }
+}
@@ -0,0 +1,61 @@
+use core::fmt::Debug;
+
+use crate::{KeyedMoveError, MoveResult};
+
+/// Extension trait which attaches the key to the error of the move as the context of [`anyhow::Error`].
+///
+/// The error of the move stays the root cause of the resulting error,
+/// so it can be recovered by [downcasting](anyhow::Error::downcast_ref).
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{AnyhowMoveResultExt, Many, MoveError, RefKind};
+///
+/// let mut numbers = [0, 1, 2];
+/// let mut many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+///
+/// let _ = many.try_move_mut(1).unwrap();
+/// let error = many.try_move_ref(1).with_key_context_in("numbers", 1).unwrap_err();
+/// assert_eq!(
+///     format!("{error:#}"),
+///     "failed to move reference out of numbers by key 1: reference was already borrowed mutably",
+/// );
+/// assert_eq!(error.downcast_ref(), Some(&MoveError::BorrowedMutably));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "anyhow")))]
+pub trait AnyhowMoveResultExt<T> {
+    /// Attaches the key to the error of the move, if any.
+    fn with_key_context<Key>(self, key: Key) -> anyhow::Result<T>
+    where
+        Key: Debug;
+
+    /// Attaches the key and the label of the collection to the error of the move, if any.
+    fn with_key_context_in<Key>(self, container: &'static str, key: Key) -> anyhow::Result<T>
+    where
+        Key: Debug;
+}
+
+impl<T> AnyhowMoveResultExt<T> for MoveResult<T> {
+    fn with_key_context<Key>(self, key: Key) -> anyhow::Result<T>
+    where
+        Key: Debug,
+    {
+        self.map_err(|error| {
+            let context = KeyedMoveError::new(key, error).context();
+            anyhow::Error::new(error).context(context)
+        })
+    }
+
+    fn with_key_context_in<Key>(self, container: &'static str, key: Key) -> anyhow::Result<T>
+    where
+        Key: Debug,
+    {
+        self.map_err(|error| {
+            let context = KeyedMoveError::new(key, error)
+                .with_container(container)
+                .context();
+            anyhow::Error::new(error).context(context)
+        })
+    }
+}
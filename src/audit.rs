@@ -0,0 +1,343 @@
+//! Provides wrapper of the collection which reports every move
+//! into a pluggable [sink](AuditSink) for post-mortem analysis of the borrow traffic.
+
+#[cfg(feature = "metrics")]
+use metrics::{Counter, Label, Level, Metadata, SharedString};
+#[cfg(feature = "metrics")]
+use std_crate::collections::HashMap;
+
+#[cfg(feature = "metrics")]
+use crate::MoveError;
+use crate::{Availability, Many, ManyAvailability, MoveResult};
+
+/// Kind of reference which was requested to be moved out of the collection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MoveKind {
+    /// Immutable reference was requested.
+    Ref,
+    /// Mutable reference was requested.
+    Mut,
+}
+
+/// Trait for receivers of the moves performed on the [`Audited`] collection.
+///
+/// This trait is implemented for closures which receive the key,
+/// the kind of requested reference and the outcome of the move.
+pub trait AuditSink<Key> {
+    /// Records the move performed on the collection by the key.
+    fn record(&mut self, key: &Key, kind: MoveKind, outcome: MoveResult<()>);
+
+    /// Records that an immutable reference was moved out of the element which held a mutable one.
+    ///
+    /// Downgrades are reported only by the [`DowngradeAudited`] wrapper,
+    /// which can check the availability of the element before the move.
+    /// Default implementation does nothing.
+    fn record_downgrade(&mut self, key: &Key) {
+        let _ = key;
+    }
+}
+
+impl<Key, F> AuditSink<Key> for F
+where
+    F: FnMut(&Key, MoveKind, MoveResult<()>),
+{
+    fn record(&mut self, key: &Key, kind: MoveKind, outcome: MoveResult<()>) {
+        self(key, kind, outcome)
+    }
+}
+
+/// Sink which exports counters of moves, downgrades and conflicts through the [`metrics`] facade.
+///
+/// Every counter is labeled with the name of the container provided by the user:
+///
+/// | Counter                     | Labels                       | Description                               |
+/// |-----------------------------|------------------------------|-------------------------------------------|
+/// | `ref_kind_moves_total`      | `container`, `kind`          | Successful moves                          |
+/// | `ref_kind_conflicts_total`  | `container`, `kind`, `error` | Moves which failed with a conflict        |
+/// | `ref_kind_not_found_total`  | `container`, `kind`          | Moves by missing keys                     |
+/// | `ref_kind_downgrades_total` | `container`                  | Immutable moves out of mutable references |
+///
+/// Downgrades are counted only if the collection is wrapped with [`DowngradeAudited`].
+/// Counters are registered once per kind and outcome and reused by the following moves.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{
+///     audit::{DowngradeAudited, MetricsSink},
+///     Many, RefKind,
+/// };
+///
+/// let mut numbers = [0, 1, 2];
+/// let many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+///
+/// let mut many = DowngradeAudited::new(many, MetricsSink::new("numbers"));
+/// let _ = many.try_move_mut(1);
+/// let _ = many.try_move_mut(1);
+/// let _ = many.try_move_ref(2);
+/// ```
+///
+/// [`metrics`]: https://docs.rs/metrics
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+#[derive(Debug, Clone)]
+pub struct MetricsSink {
+    label: SharedString,
+    counters: HashMap<(MoveKind, Option<MoveError>), Counter>,
+    downgrades: Option<Counter>,
+}
+
+#[cfg(feature = "metrics")]
+impl MetricsSink {
+    /// Creates new sink which labels counters with the provided name of the container.
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        let label = label.into();
+        Self {
+            label,
+            counters: HashMap::new(),
+            downgrades: None,
+        }
+    }
+
+    /// Returns the name of the container which labels the counters.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl MetricsSink {
+    fn register(
+        &self,
+        name: &'static str,
+        kind: Option<MoveKind>,
+        error: Option<&'static str>,
+    ) -> Counter {
+        let container = Label::new("container", self.label.clone());
+        let kind = kind.map(|kind| match kind {
+            MoveKind::Ref => Label::new("kind", "ref"),
+            MoveKind::Mut => Label::new("kind", "mut"),
+        });
+        let error = error.map(|error| Label::new("error", error));
+        let labels = [Some(container), kind, error].into_iter().flatten();
+        let key = metrics::Key::from_parts(name, labels.collect::<std_crate::vec::Vec<_>>());
+        metrics::with_recorder(|recorder| recorder.register_counter(&key, &METADATA))
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<Key> AuditSink<Key> for MetricsSink {
+    fn record(&mut self, _: &Key, kind: MoveKind, outcome: MoveResult<()>) {
+        let error = outcome.err();
+        if let Some(counter) = self.counters.get(&(kind, error)) {
+            return counter.increment(1);
+        }
+        let (name, label) = match error {
+            None => ("ref_kind_moves_total", None),
+            Some(MoveError::BorrowedImmutably) => {
+                ("ref_kind_conflicts_total", Some("borrowed_immutably"))
+            }
+            Some(MoveError::BorrowedMutably) => {
+                ("ref_kind_conflicts_total", Some("borrowed_mutably"))
+            }
+            Some(MoveError::Poisoned) => ("ref_kind_conflicts_total", Some("poisoned")),
+            Some(MoveError::NotFound) => ("ref_kind_not_found_total", None),
+        };
+        let counter = self.register(name, Some(kind), label);
+        counter.increment(1);
+        self.counters.insert((kind, error), counter);
+    }
+
+    fn record_downgrade(&mut self, _: &Key) {
+        let counter = match &self.downgrades {
+            Some(counter) => counter,
+            None => {
+                let counter = self.register("ref_kind_downgrades_total", None, None);
+                self.downgrades.insert(counter)
+            }
+        };
+        counter.increment(1);
+    }
+}
+
+#[cfg(feature = "metrics")]
+static METADATA: Metadata<'static> =
+    Metadata::new(module_path!(), Level::INFO, Some(module_path!()));
+
+/// Wrapper of the collection which reports every successful and failed move into the sink.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{
+///     audit::{Audited, MoveKind},
+///     Many, MoveError, RefKind,
+/// };
+///
+/// let mut numbers = [0, 1, 2];
+/// let many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+///
+/// let mut log = Vec::new();
+/// let mut many = Audited::new(many, |&key: &usize, kind, outcome| log.push((key, kind, outcome)));
+/// let _ = many.try_move_mut(1);
+/// let _ = many.try_move_ref(1);
+/// drop(many);
+///
+/// assert_eq!(
+///     log,
+///     [
+///         (1, MoveKind::Mut, Ok(())),
+///         (1, MoveKind::Ref, Err(MoveError::BorrowedMutably)),
+///     ],
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Audited<M, S> {
+    many: M,
+    sink: S,
+}
+
+impl<M, S> Audited<M, S> {
+    /// Creates new wrapper of the collection which reports moves into the sink.
+    pub fn new(many: M, sink: S) -> Self {
+        Self { many, sink }
+    }
+
+    /// Returns an immutable reference to the underlying collection.
+    pub fn get_ref(&self) -> &M {
+        &self.many
+    }
+
+    /// Returns an immutable reference to the sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Returns a mutable reference to the sink.
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Returns the underlying collection and the sink, consuming the `self` value.
+    pub fn into_inner(self) -> (M, S) {
+        (self.many, self.sink)
+    }
+}
+
+/// Implementation of [`Many`] trait for [`Audited`] which reports every move into the sink.
+impl<'a, M, S, Key> Many<'a, Key> for Audited<M, S>
+where
+    M: Many<'a, Key>,
+    S: AuditSink<Key>,
+    Key: Clone,
+{
+    type Ref = M::Ref;
+
+    fn try_move_ref(&mut self, key: Key) -> MoveResult<Self::Ref> {
+        let result = self.many.try_move_ref(key.clone());
+        let outcome = result.as_ref().map(|_| ()).map_err(|error| *error);
+        self.sink.record(&key, MoveKind::Ref, outcome);
+        result
+    }
+
+    type Mut = M::Mut;
+
+    fn try_move_mut(&mut self, key: Key) -> MoveResult<Self::Mut> {
+        let result = self.many.try_move_mut(key.clone());
+        let outcome = result.as_ref().map(|_| ()).map_err(|error| *error);
+        self.sink.record(&key, MoveKind::Mut, outcome);
+        result
+    }
+}
+
+/// Wrapper of the collection which reports every move into the sink like [`Audited`] does,
+/// and also reports [downgrades](AuditSink::record_downgrade) of mutable references to immutable ones.
+///
+/// Availability of the element is checked before each immutable move,
+/// so the collection must implement [`ManyAvailability`] trait.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{
+///     audit::{AuditSink, DowngradeAudited, MoveKind},
+///     Many, MoveResult, RefKind,
+/// };
+///
+/// struct Downgrades(Vec<usize>);
+///
+/// impl AuditSink<usize> for Downgrades {
+///     fn record(&mut self, _: &usize, _: MoveKind, _: MoveResult<()>) {}
+///
+///     fn record_downgrade(&mut self, &key: &usize) {
+///         self.0.push(key);
+///     }
+/// }
+///
+/// let mut numbers = [0, 1, 2];
+/// let mut many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+/// let _ = many.try_move_ref(2);
+///
+/// let mut many = DowngradeAudited::new(many, Downgrades(Vec::new()));
+/// let _ = many.try_move_ref(0);
+/// let _ = many.try_move_ref(0);
+/// let _ = many.try_move_ref(2);
+/// assert_eq!(many.sink().0, [0]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DowngradeAudited<M, S> {
+    audited: Audited<M, S>,
+}
+
+impl<M, S> DowngradeAudited<M, S> {
+    /// Creates new wrapper of the collection which reports moves and downgrades into the sink.
+    pub fn new(many: M, sink: S) -> Self {
+        let audited = Audited::new(many, sink);
+        Self { audited }
+    }
+
+    /// Returns an immutable reference to the underlying collection.
+    pub fn get_ref(&self) -> &M {
+        self.audited.get_ref()
+    }
+
+    /// Returns an immutable reference to the sink.
+    pub fn sink(&self) -> &S {
+        self.audited.sink()
+    }
+
+    /// Returns a mutable reference to the sink.
+    pub fn sink_mut(&mut self) -> &mut S {
+        self.audited.sink_mut()
+    }
+
+    /// Returns the underlying collection and the sink, consuming the `self` value.
+    pub fn into_inner(self) -> (M, S) {
+        self.audited.into_inner()
+    }
+}
+
+/// Implementation of [`Many`] trait for [`DowngradeAudited`] which reports every move and downgrade into the sink.
+impl<'a, M, S, Key> Many<'a, Key> for DowngradeAudited<M, S>
+where
+    M: Many<'a, Key> + ManyAvailability<Key>,
+    S: AuditSink<Key>,
+    Key: Clone,
+{
+    type Ref = M::Ref;
+
+    fn try_move_ref(&mut self, key: Key) -> MoveResult<Self::Ref> {
+        let availability = self.audited.many.availability(&key);
+        let result = self.audited.try_move_ref(key.clone());
+        if result.is_ok() && availability == Some(Availability::Mut) {
+            self.audited.sink.record_downgrade(&key);
+        }
+        result
+    }
+
+    type Mut = M::Mut;
+
+    fn try_move_mut(&mut self, key: Key) -> MoveResult<Self::Mut> {
+        self.audited.try_move_mut(key)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Mut, Ref, RefKind};
+
+/// Availability state of the element in the collection of optional [`RefKind`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Availability {
+    /// Element holds mutable reference.
+    Mut,
+    /// Element holds immutable reference.
+    Ref,
+    /// Mutable reference was already moved out of the element.
+    Moved,
+}
+
+impl Availability {
+    /// Returns the availability state of the element.
+    pub fn of<T>(item: &Option<RefKind<'_, T>>) -> Self
+    where
+        T: ?Sized,
+    {
+        match item {
+            Some(Mut(_)) => Self::Mut,
+            Some(Ref(_)) => Self::Ref,
+            None => Self::Moved,
+        }
+    }
+}
+
+/// Trait for collections which can report the [availability](Availability) of the element
+/// by the key without moving the reference out of it.
+///
+/// This allows to check several keys up front, so that references can be moved out
+/// either all at once or none of them, as [`AccessSet::claim`](crate::access::AccessSet::claim) does.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Availability, Many, ManyAvailability, RefKind};
+///
+/// let mut numbers = [0, 1];
+/// let mut many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+/// let _ = many.try_move_ref(1);
+///
+/// assert_eq!(many.availability(&0), Some(Availability::Mut));
+/// assert_eq!(many.availability(&1), Some(Availability::Ref));
+/// assert_eq!(many.availability(&2), None);
+/// ```
+pub trait ManyAvailability<Key> {
+    /// Returns the availability of the element by the key,
+    /// or [`None`] if the collection contains no element by this key.
+    fn availability(&self, key: &Key) -> Option<Availability>;
+}
+
+/// Optional [`RefKind`] holds the only element for any key, in the same way as it implements [`Many`](crate::Many).
+impl<T, Key> ManyAvailability<Key> for Option<RefKind<'_, T>>
+where
+    T: ?Sized,
+{
+    fn availability(&self, _: &Key) -> Option<Availability> {
+        Some(Availability::of(self))
+    }
+}
+
+/// Implementation of [`ManyAvailability`] trait for [slice](prim@slice).
+impl<V> ManyAvailability<usize> for [V]
+where
+    V: ManyAvailability<usize>,
+{
+    fn availability(&self, key: &usize) -> Option<Availability> {
+        self.get(*key)?.availability(key)
+    }
+}
+
+/// Implementation of [`ManyAvailability`] trait for [array](prim@array).
+impl<V, const N: usize> ManyAvailability<usize> for [V; N]
+where
+    V: ManyAvailability<usize>,
+{
+    fn availability(&self, key: &usize) -> Option<Availability> {
+        self.as_slice().availability(key)
+    }
+}
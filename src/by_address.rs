@@ -0,0 +1,114 @@
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// Wrapper of the reference (e.g. [`RefKind`](crate::RefKind)) which compares and hashes it by the address of its referent.
+///
+/// Equality, ordering and hashing of the wrapper use the address of the value
+/// instead of the value itself, so comparisons of the referent type are neither required nor invoked.
+/// This allows to use references as identity keys of sets and maps,
+/// for example to find out if the same object was borrowed twice.
+///
+/// Metadata of pointers to unsized types is ignored, so only the address of the value is compared.
+/// Note that distinct values can share the address:
+/// this is the case for values of zero-sized types
+/// and for a struct and its field placed at offset zero.
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::BTreeSet;
+///
+/// use ref_kind::{ByAddress, RefKind};
+///
+/// let first = String::from("same");
+/// let second = String::from("same");
+///
+/// let borrows = [RefKind::from(&first), RefKind::from(&second), RefKind::from(&first)];
+/// let unique: BTreeSet<_> = borrows.into_iter().map(ByAddress).collect();
+/// assert_eq!(unique.len(), 2);
+/// assert_ne!(ByAddress(&first), ByAddress(&second));
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ByAddress<P>(pub P);
+
+impl<P> ByAddress<P>
+where
+    P: Deref,
+{
+    /// Returns the address of the referent.
+    pub fn addr(&self) -> *const () {
+        let pointer: *const P::Target = &*self.0;
+        pointer.cast()
+    }
+}
+
+impl<P> ByAddress<P> {
+    /// Returns the wrapped reference, consuming the `self` value.
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P> Deref for ByAddress<P>
+where
+    P: Deref,
+{
+    type Target = P::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P> PartialEq for ByAddress<P>
+where
+    P: Deref,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
+    }
+}
+
+impl<P> Eq for ByAddress<P> where P: Deref {}
+
+impl<P> PartialOrd for ByAddress<P>
+where
+    P: Deref,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P> Ord for ByAddress<P>
+where
+    P: Deref,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl<P> Hash for ByAddress<P>
+where
+    P: Deref,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.addr().hash(state);
+    }
+}
+
+/// Returns the address of the value, ignoring metadata of pointers to unsized types.
+#[cfg(feature = "alloc")]
+pub(crate) fn address<T>(value: &T) -> usize
+where
+    T: ?Sized,
+{
+    (value as *const T).cast::<()>() as usize
+}
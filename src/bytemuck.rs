@@ -0,0 +1,39 @@
+use bytemuck::{Pod, PodCastError};
+
+use crate::{Mut, Ref, RefKind};
+
+impl<'a, A> RefKind<'a, [A]>
+where
+    A: Pod,
+{
+    /// Tries to cast the slice of one [`Pod`] type into the slice of another,
+    /// preserving the kind of reference, consuming the `self` value.
+    ///
+    /// See [`bytemuck::try_cast_slice`] and [`bytemuck::try_cast_slice_mut`] for details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::RefKind;
+    ///
+    /// let mut bytes = [1_u8, 0, 2, 0];
+    /// let kind = RefKind::from(&mut bytes[..]);
+    ///
+    /// let mut halves = kind.try_cast_slice::<u16>().unwrap();
+    /// assert!(halves.is_mut());
+    /// assert_eq!(halves.len(), 2);
+    /// halves.get_mut().unwrap()[1] = 0;
+    /// assert_eq!(bytes, [1, 0, 0, 0]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn try_cast_slice<B>(self) -> Result<RefKind<'a, [B]>, PodCastError>
+    where
+        B: Pod,
+    {
+        let kind = match self {
+            Ref(shared) => Ref(bytemuck::try_cast_slice(shared)?),
+            Mut(unique) => Mut(bytemuck::try_cast_slice_mut(unique)?),
+        };
+        Ok(kind)
+    }
+}
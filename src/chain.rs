@@ -0,0 +1,86 @@
+use crate::{Many, MoveResult};
+
+/// Type of key for [`ManyChain`] which selects one of the chained collections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChainKey<A, B> {
+    /// Pass key to the first collection.
+    First(A),
+    /// Pass key to the second collection.
+    Second(B),
+}
+
+/// Adapter which treats two collections as one.
+///
+/// Both collections should move out references of the same types,
+/// while their keys can be of different types.
+/// This is useful when code written against one collection should also span another one,
+/// e.g. static registry and per-frame overlay.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{ChainKey, Many, ManyChain, MoveError, RefKind};
+///
+/// let (mut statics, mut overlay) = ([0, 1], [10, 11, 12]);
+/// let statics = statics.each_mut().map(|i| Some(RefKind::from(i)));
+/// let overlay = overlay.each_mut().map(|i| Some(RefKind::from(i)));
+/// let mut many = ManyChain::new(statics, overlay);
+///
+/// // Type of the key of another collection cannot be inferred from one variant
+/// type Key = ChainKey<usize, usize>;
+/// assert_eq!(many.try_move_mut(Key::First(1)), Ok(Some(&mut 1)));
+/// assert_eq!(many.try_move_mut(Key::Second(1)), Ok(Some(&mut 11)));
+/// assert_eq!(many.try_move_ref(Key::First(1)), Err(MoveError::BorrowedMutably));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ManyChain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> ManyChain<A, B> {
+    /// Creates new adapter of two collections.
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns immutable references to the underlying collections.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Returns mutable references to the underlying collections.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Returns the underlying collections, consuming the `self` value.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+/// Implementation of [`Many`] trait for [`ManyChain`] which passes the key into the selected collection.
+impl<'a, A, B, KeyA, KeyB> Many<'a, ChainKey<KeyA, KeyB>> for ManyChain<A, B>
+where
+    A: Many<'a, KeyA>,
+    B: Many<'a, KeyB, Ref = A::Ref, Mut = A::Mut>,
+{
+    type Ref = A::Ref;
+
+    fn try_move_ref(&mut self, key: ChainKey<KeyA, KeyB>) -> MoveResult<Self::Ref> {
+        match key {
+            ChainKey::First(key) => self.first.try_move_ref(key),
+            ChainKey::Second(key) => self.second.try_move_ref(key),
+        }
+    }
+
+    type Mut = A::Mut;
+
+    fn try_move_mut(&mut self, key: ChainKey<KeyA, KeyB>) -> MoveResult<Self::Mut> {
+        match key {
+            ChainKey::First(key) => self.first.try_move_mut(key),
+            ChainKey::Second(key) => self.second.try_move_mut(key),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use alloc_crate::vec::Vec;
+
+/// Trait for dense collections which can remove their empty entries,
+/// remapping keys of the rest of entries.
+///
+/// Entries which mutable references were moved out (or which were removed) are left as [`None`],
+/// so long-lived dense collections accumulate holes over time.
+/// Compaction removes these holes and returns a table which maps old keys to new ones.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Compact, Many, RefKind};
+///
+/// let mut numbers = [0, 1, 2, 3];
+/// let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+/// let _ = many.try_move_mut(0);
+/// let _ = many.try_move_mut(2);
+///
+/// let remap = many.compact();
+/// assert_eq!(remap, [None, Some(0), None, Some(1)]);
+/// assert_eq!(many.try_move_mut(remap[3].unwrap()), Ok(Some(&mut 3)));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait Compact {
+    /// Removes empty entries, preserving the order of the rest of entries.
+    ///
+    /// Returns the table which maps each old key into the new key of the entry,
+    /// or [`None`] if the entry was removed.
+    fn compact(&mut self) -> Vec<Option<usize>>;
+}
+
+impl<V> Compact for Vec<Option<V>> {
+    fn compact(&mut self) -> Vec<Option<usize>> {
+        let mut next = 0;
+        let remap = self
+            .iter()
+            .map(|item| {
+                let key = item.as_ref().map(|_| next);
+                next += usize::from(item.is_some());
+                key
+            })
+            .collect();
+        self.retain(Option::is_some);
+        remap
+    }
+}
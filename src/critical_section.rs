@@ -0,0 +1,170 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::{Many, MoveError, MoveResult};
+
+/// Wrapper of the collection which implements [`Many`] trait
+/// and can be shared between main code and interrupt handlers.
+///
+/// Each move is performed inside of a [critical section](critical_section::with),
+/// so references can be moved out through a shared reference, e.g. from a `static` item.
+/// Conflicts are reported as [`MoveError`] in the same way as by the underlying collection.
+///
+/// Wrapper does not allocate, so it is suitable for fixed-capacity collections such as arrays.
+/// On multicore targets (e.g. RP2040 or ESP32) critical section implementations also lock out other cores,
+/// so each core can claim its own keys while conflicts are still reported as errors.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{CriticalMany, MoveError, RefKind};
+///
+/// let mut numbers = [0, 1, 2, 3];
+/// let many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+/// let many = CriticalMany::new(many);
+///
+/// // Both the main code and the interrupt handler can move references out
+/// let number = many.move_mut(1).unwrap();
+/// *number += 10;
+/// assert_eq!(many.try_move_ref(1), Err(MoveError::BorrowedMutably));
+/// assert_eq!(numbers[1], 11);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "critical-section")))]
+#[derive(Debug)]
+pub struct CriticalMany<M> {
+    inner: Mutex<RefCell<M>>,
+}
+
+impl<M> CriticalMany<M> {
+    /// Creates new wrapper of the provided collection.
+    pub const fn new(many: M) -> Self {
+        let inner = Mutex::new(RefCell::new(many));
+        Self { inner }
+    }
+
+    /// Tries to move an immutable reference out of the collection inside of a critical section.
+    ///
+    /// See [`Many::try_move_ref`] for details.
+    pub fn try_move_ref<'a, Key>(&self, key: Key) -> MoveResult<M::Ref>
+    where
+        M: Many<'a, Key>,
+    {
+        critical_section::with(|cs| {
+            let mut many = self
+                .inner
+                .borrow(cs)
+                .try_borrow_mut()
+                .map_err(|_| MoveError::BorrowedMutably)?;
+            many.try_move_ref(key)
+        })
+    }
+
+    /// Moves an immutable reference out of the collection inside of a critical section.
+    ///
+    /// See [`Many::move_ref`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the collection.
+    #[track_caller]
+    pub fn move_ref<'a, Key>(&self, key: Key) -> M::Ref
+    where
+        M: Many<'a, Key>,
+    {
+        match self.try_move_ref(key) {
+            Ok(result) => result,
+            Err(error) => crate::many::move_panic(error),
+        }
+    }
+
+    /// Tries to move a mutable reference out of the collection inside of a critical section.
+    ///
+    /// See [`Many::try_move_mut`] for details.
+    pub fn try_move_mut<'a, Key>(&self, key: Key) -> MoveResult<M::Mut>
+    where
+        M: Many<'a, Key>,
+    {
+        critical_section::with(|cs| {
+            let mut many = self
+                .inner
+                .borrow(cs)
+                .try_borrow_mut()
+                .map_err(|_| MoveError::BorrowedMutably)?;
+            many.try_move_mut(key)
+        })
+    }
+
+    /// Moves a mutable reference out of the collection inside of a critical section.
+    ///
+    /// See [`Many::move_mut`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the collection
+    /// or the value was already borrowed as immutable.
+    #[track_caller]
+    pub fn move_mut<'a, Key>(&self, key: Key) -> M::Mut
+    where
+        M: Many<'a, Key>,
+    {
+        match self.try_move_mut(key) {
+            Ok(result) => result,
+            Err(error) => crate::many::move_panic(error),
+        }
+    }
+
+    /// Replaces the underlying collection inside of a critical section, returning the old one.
+    ///
+    /// This allows to fill the wrapper stored in a `static` item after it was created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the collection is being used by another move at the same time,
+    /// which is possible only if the move was reentered by the collection itself.
+    #[track_caller]
+    pub fn replace(&self, many: M) -> M {
+        critical_section::with(|cs| self.inner.borrow(cs).replace(many))
+    }
+
+    /// Tries to replace the underlying collection inside of a critical section, returning the old one.
+    ///
+    /// See [`replace`](CriticalMany::replace) for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns the provided collection back if the collection is being used by another move at the same time.
+    pub fn try_replace(&self, many: M) -> Result<M, M> {
+        critical_section::with(|cs| match self.inner.borrow(cs).try_borrow_mut() {
+            Ok(mut inner) => Ok(core::mem::replace(&mut *inner, many)),
+            Err(_) => Err(many),
+        })
+    }
+
+    /// Returns a mutable reference to the underlying collection.
+    ///
+    /// No critical section is needed because this call borrows the wrapper mutably.
+    pub fn get_mut(&mut self) -> &mut M {
+        self.inner.get_mut().get_mut()
+    }
+
+    /// Returns the underlying collection, consuming the `self` value.
+    pub fn into_inner(self) -> M {
+        self.inner.into_inner().into_inner()
+    }
+}
+
+impl<M> Default for CriticalMany<M>
+where
+    M: Default,
+{
+    fn default() -> Self {
+        Self::new(M::default())
+    }
+}
+
+impl<M> From<M> for CriticalMany<M> {
+    fn from(many: M) -> Self {
+        Self::new(many)
+    }
+}
@@ -0,0 +1,200 @@
+use alloc_crate::vec::Vec;
+use core::iter::FusedIterator;
+
+use crate::{Many, MoveError, MoveResult};
+
+/// Cursor which moves mutable references out of the slice sequentially,
+/// while still allowing keyed moves of the elements behind the cursor.
+///
+/// Elements can be passed by the cursor in two ways:
+/// - moved out by [`next`](Iterator::next), so they cannot be moved out again;
+/// - skipped by [`advance`](ManyCursor::advance), so they stay behind the cursor
+///   and can be moved out later by their index through the [`Many`] implementation.
+///
+/// Elements ahead of the cursor cannot be moved out by index:
+/// their moves succeed with [`None`] as if they were out of bounds.
+/// Skipped elements stay in the borrowed slice, which is split with [`split_at_mut`](slice::split_at_mut)
+/// only when some element is moved out, so the cursor stores runs of skipped elements
+/// between the moved out ones instead of the optional reference for every passed element.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Many, ManyCursor, MoveError};
+///
+/// let mut tokens = ["let", "x", "=", "1"];
+/// let mut cursor = ManyCursor::new(&mut tokens);
+///
+/// let keyword = cursor.next().unwrap();
+/// assert_eq!(*cursor.advance().unwrap(), "x");
+/// let operator = cursor.next().unwrap();
+///
+/// // Look back at the skipped identifier
+/// let name = cursor.try_move_mut(1).unwrap().unwrap();
+/// *name = "y";
+/// assert_eq!(cursor.try_move_ref(0), Err(MoveError::BorrowedMutably));
+/// assert_eq!(cursor.try_move_ref(3), Ok(None));
+///
+/// assert_eq!((*keyword, *operator), ("let", "="));
+/// assert_eq!(tokens[1], "y");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct ManyCursor<'a, T> {
+    /// Elements starting at `offset`: skipped ones before the cursor and the ones ahead of it.
+    tail: &'a mut [T],
+    offset: usize,
+    position: usize,
+    /// Runs of skipped elements split off the tail, with indices of their first elements, in ascending order.
+    skipped: Vec<(usize, &'a mut [T])>,
+    /// Elements which were moved out as immutable, in ascending order of their indices.
+    shared: Vec<(usize, &'a T)>,
+}
+
+impl<'a, T> ManyCursor<'a, T> {
+    /// Creates new cursor at the start of the slice.
+    pub fn new(slice: &'a mut [T]) -> Self {
+        Self {
+            tail: slice,
+            offset: 0,
+            position: 0,
+            skipped: Vec::new(),
+            shared: Vec::new(),
+        }
+    }
+
+    /// Returns the count of elements passed by the cursor.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the count of elements ahead of the cursor.
+    pub fn remaining(&self) -> usize {
+        self.tail.len() - (self.position - self.offset)
+    }
+
+    /// Returns the count of bytes allocated on the heap by the cursor.
+    ///
+    /// This is an estimation based on the capacity of the runs of skipped elements
+    /// and immutable references moved out of them, because elements themselves are borrowed from the slice.
+    pub fn allocated_bytes(&self) -> usize {
+        self.skipped.capacity() * size_of::<(usize, &'a mut [T])>()
+            + self.shared.capacity() * size_of::<(usize, &'a T)>()
+    }
+
+    /// Skips the next element, leaving it behind the cursor available to be moved out by its index.
+    ///
+    /// Returns a temporary reference to the skipped element,
+    /// or [`None`] if there are no elements ahead of the cursor.
+    pub fn advance(&mut self) -> Option<&mut T> {
+        let unique = self.tail.get_mut(self.position - self.offset)?;
+        self.position += 1;
+        Some(unique)
+    }
+
+    /// Returns the elements ahead of the cursor, consuming the `self` value.
+    ///
+    /// Elements behind the cursor which were skipped cannot be moved out anymore.
+    pub fn into_remaining(self) -> &'a mut [T] {
+        let (_, ahead) = self.tail.split_at_mut(self.position - self.offset);
+        ahead
+    }
+
+    /// Moves the skipped element out of the slice by its index,
+    /// or returns [`None`] if the element was already moved out.
+    fn take(&mut self, key: usize) -> Option<&'a mut T> {
+        if key >= self.offset {
+            let (before, unique) = split_out(&mut self.tail, key - self.offset)?;
+            if !before.is_empty() {
+                self.skipped.push((self.offset, before));
+            }
+            self.offset = key + 1;
+            return Some(unique);
+        }
+
+        let index = self.skipped.partition_point(|&(start, _)| start <= key);
+        let (start, run) = self.skipped.get_mut(index.checked_sub(1)?)?;
+        let (before, unique) = split_out(run, key - *start)?;
+        let start = core::mem::replace(start, key + 1);
+        if run.is_empty() {
+            self.skipped.remove(index - 1);
+        }
+        if !before.is_empty() {
+            self.skipped.insert(index - 1, (start, before));
+        }
+        Some(unique)
+    }
+
+    fn shared(&self, key: usize) -> Option<&'a T> {
+        let index = self
+            .shared
+            .binary_search_by_key(&key, |&(key, _)| key)
+            .ok()?;
+        Some(self.shared[index].1)
+    }
+}
+
+/// Splits the element out of the slice by its index, leaving the elements after it in the slice.
+///
+/// Returns the elements before it together with the element,
+/// or [`None`] if the index is out of bounds, in which case the slice is left intact.
+fn split_out<'a, T>(slice: &mut &'a mut [T], index: usize) -> Option<(&'a mut [T], &'a mut T)> {
+    if index >= slice.len() {
+        return None;
+    }
+    let (before, rest) = core::mem::take(slice).split_at_mut(index);
+    let (unique, after) = rest.split_first_mut()?;
+    *slice = after;
+    Some((before, unique))
+}
+
+impl<'a, T> Iterator for ManyCursor<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let unique = self.take(self.position)?;
+        self.position += 1;
+        Some(unique)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for ManyCursor<'_, T> {}
+
+impl<T> FusedIterator for ManyCursor<'_, T> {}
+
+/// Implementation of [`Many`] trait for [`ManyCursor`] which moves out elements behind the cursor by their index.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, T> Many<'a, usize> for ManyCursor<'a, T> {
+    type Ref = Option<&'a T>;
+
+    fn try_move_ref(&mut self, key: usize) -> MoveResult<Self::Ref> {
+        if key >= self.position {
+            return Ok(None);
+        }
+        if let Some(shared) = self.shared(key) {
+            return Ok(Some(shared));
+        }
+        let shared: &'a T = self.take(key).ok_or(MoveError::BorrowedMutably)?;
+        let index = self.shared.partition_point(|&(shared, _)| shared < key);
+        self.shared.insert(index, (key, shared));
+        Ok(Some(shared))
+    }
+
+    type Mut = Option<&'a mut T>;
+
+    fn try_move_mut(&mut self, key: usize) -> MoveResult<Self::Mut> {
+        if key >= self.position {
+            return Ok(None);
+        }
+        if self.shared(key).is_some() {
+            return Err(MoveError::BorrowedImmutably);
+        }
+        let unique = self.take(key).ok_or(MoveError::BorrowedMutably)?;
+        Ok(Some(unique))
+    }
+}
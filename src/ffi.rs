@@ -0,0 +1,318 @@
+//! Provides C interface over the registry of pointers keyed by bytes,
+//! so engines with C plugin interfaces can expose the borrow registry to plugins.
+//!
+//! References of C code are raw pointers which cannot be checked by the borrow checker,
+//! so [`RefKindMap`] stores them as pointers and only tracks their availability
+//! in the same way as [`Many`](crate::Many) collections track availability of references:
+//! mutable pointer can be moved out only once, and immutable pointers can be copied any number of times.
+//!
+//! Functions with `extern "C"` ABI operate on the opaque handle of the map,
+//! which is created by [`ref_kind_map_new`] and destroyed by [`ref_kind_map_free`].
+//! Outcome of each function is reported as [`RefKindStatus`].
+//!
+//! This module contains `unsafe` code, because the handle and the keys are passed by raw pointers.
+//!
+//! ## Example
+//!
+//! ```
+//! use core::ffi::c_void;
+//!
+//! use ref_kind::ffi::*;
+//!
+//! let mut number = 42;
+//! let key = b"number";
+//!
+//! let map = ref_kind_map_new();
+//! let mut unique = core::ptr::null_mut();
+//! // SAFETY: handle was created above, key points to `key.len()` bytes and `unique` is writable
+//! unsafe {
+//!     let pointer = (&mut number as *mut i32).cast::<c_void>();
+//!     let status = ref_kind_map_insert_mut(map, key.as_ptr(), key.len(), pointer);
+//!     assert_eq!(status, RefKindStatus::Ok);
+//!
+//!     let status = ref_kind_map_move_mut(map, key.as_ptr(), key.len(), &mut unique);
+//!     assert_eq!(status, RefKindStatus::Ok);
+//!     let status = ref_kind_map_move_mut(map, key.as_ptr(), key.len(), &mut unique);
+//!     assert_eq!(status, RefKindStatus::BorrowedMutably);
+//!
+//!     ref_kind_map_free(map);
+//! }
+//! assert_eq!(unique.cast::<i32>(), &mut number as *mut i32);
+//! ```
+
+#![allow(unsafe_code)]
+
+use core::{ffi::c_void, slice};
+use std_crate::{boxed::Box, collections::HashMap};
+
+use crate::{Availability, MoveError, MoveResult};
+
+/// Registry of pointers keyed by bytes which tracks availability of every pointer.
+///
+/// See [module documentation](self) for details.
+#[derive(Debug, Default)]
+pub struct RefKindMap {
+    slots: HashMap<Box<[u8]>, Slot>,
+}
+
+#[derive(Debug)]
+struct Slot {
+    pointer: *mut c_void,
+    state: Availability,
+}
+
+impl RefKindMap {
+    /// Creates new empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the count of pointers stored in the map, including the moved out ones.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Checks if the map contains no pointers.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Inserts an immutable pointer by the key, replacing the previous pointer if any.
+    pub fn insert_ref(&mut self, key: &[u8], pointer: *const c_void) {
+        let pointer = pointer.cast_mut();
+        let slot = Slot {
+            pointer,
+            state: Availability::Ref,
+        };
+        self.slots.insert(key.into(), slot);
+    }
+
+    /// Inserts a mutable pointer by the key, replacing the previous pointer if any.
+    pub fn insert_mut(&mut self, key: &[u8], pointer: *mut c_void) {
+        let slot = Slot {
+            pointer,
+            state: Availability::Mut,
+        };
+        self.slots.insert(key.into(), slot);
+    }
+
+    /// Tries to move an immutable pointer out of the map by the key.
+    ///
+    /// Mutable pointer is replaced with immutable one, in the same way as by [`Many::try_move_ref`](crate::Many::try_move_ref).
+    /// Returns [`None`] if the map contains no pointer by the key.
+    pub fn try_move_ref(&mut self, key: &[u8]) -> MoveResult<Option<*const c_void>> {
+        let slot = match self.slots.get_mut(key) {
+            Some(slot) => slot,
+            None => return Ok(None),
+        };
+        match slot.state {
+            Availability::Mut | Availability::Ref => {
+                slot.state = Availability::Ref;
+                Ok(Some(slot.pointer.cast_const()))
+            }
+            Availability::Moved => Err(MoveError::BorrowedMutably),
+        }
+    }
+
+    /// Tries to move a mutable pointer out of the map by the key.
+    ///
+    /// Returns [`None`] if the map contains no pointer by the key.
+    pub fn try_move_mut(&mut self, key: &[u8]) -> MoveResult<Option<*mut c_void>> {
+        let slot = match self.slots.get_mut(key) {
+            Some(slot) => slot,
+            None => return Ok(None),
+        };
+        match slot.state {
+            Availability::Mut => {
+                slot.state = Availability::Moved;
+                Ok(Some(slot.pointer))
+            }
+            Availability::Ref => Err(MoveError::BorrowedImmutably),
+            Availability::Moved => Err(MoveError::BorrowedMutably),
+        }
+    }
+}
+
+/// Outcome of the function of C interface.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RefKindStatus {
+    /// Function succeeded.
+    Ok = 0,
+    /// Pointer was already moved out of the map as immutable.
+    BorrowedImmutably = 1,
+    /// Pointer was already moved out of the map as mutable.
+    BorrowedMutably = 2,
+    /// Map contains no pointer by the key.
+    NotFound = 3,
+    /// Some of the required pointers is null.
+    NullPointer = 4,
+}
+
+impl From<MoveError> for RefKindStatus {
+    fn from(error: MoveError) -> Self {
+        match error {
+            MoveError::BorrowedImmutably => Self::BorrowedImmutably,
+            MoveError::BorrowedMutably | MoveError::Poisoned => Self::BorrowedMutably,
+            MoveError::NotFound => Self::NotFound,
+        }
+    }
+}
+
+/// Creates new empty map, returning its handle.
+///
+/// The handle must be destroyed by [`ref_kind_map_free`].
+#[no_mangle]
+pub extern "C" fn ref_kind_map_new() -> *mut RefKindMap {
+    Box::into_raw(Box::default())
+}
+
+/// Destroys the map by its handle. Null handle is ignored.
+///
+/// # Safety
+///
+/// Handle must be either null or created by [`ref_kind_map_new`] and not destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ref_kind_map_free(map: *mut RefKindMap) {
+    if !map.is_null() {
+        // SAFETY: the caller guarantees that the handle was created by `Box::into_raw` and is not destroyed yet
+        drop(unsafe { Box::from_raw(map) });
+    }
+}
+
+/// Inserts an immutable pointer by the key, replacing the previous pointer if any.
+///
+/// Returns [`RefKindStatus::NullPointer`] if the handle or the value is null.
+///
+/// # Safety
+///
+/// Handle must be created by [`ref_kind_map_new`] and not destroyed yet.
+/// Key must point to `key_len` readable bytes, or be null if `key_len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn ref_kind_map_insert_ref(
+    map: *mut RefKindMap,
+    key: *const u8,
+    key_len: usize,
+    value: *const c_void,
+) -> RefKindStatus {
+    // SAFETY: the caller upholds the requirements of the handle and the key
+    let (Some(map), Some(key)) = (unsafe { map.as_mut() }, unsafe { key_bytes(key, key_len) })
+    else {
+        return RefKindStatus::NullPointer;
+    };
+    if value.is_null() {
+        return RefKindStatus::NullPointer;
+    }
+    map.insert_ref(key, value);
+    RefKindStatus::Ok
+}
+
+/// Inserts a mutable pointer by the key, replacing the previous pointer if any.
+///
+/// Returns [`RefKindStatus::NullPointer`] if the handle or the value is null.
+///
+/// # Safety
+///
+/// Handle must be created by [`ref_kind_map_new`] and not destroyed yet.
+/// Key must point to `key_len` readable bytes, or be null if `key_len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn ref_kind_map_insert_mut(
+    map: *mut RefKindMap,
+    key: *const u8,
+    key_len: usize,
+    value: *mut c_void,
+) -> RefKindStatus {
+    // SAFETY: the caller upholds the requirements of the handle and the key
+    let (Some(map), Some(key)) = (unsafe { map.as_mut() }, unsafe { key_bytes(key, key_len) })
+    else {
+        return RefKindStatus::NullPointer;
+    };
+    if value.is_null() {
+        return RefKindStatus::NullPointer;
+    }
+    map.insert_mut(key, value);
+    RefKindStatus::Ok
+}
+
+/// Moves an immutable pointer out of the map by the key, writing it into `out`.
+///
+/// Nothing is written into `out` unless [`RefKindStatus::Ok`] is returned.
+///
+/// # Safety
+///
+/// Handle must be created by [`ref_kind_map_new`] and not destroyed yet.
+/// Key must point to `key_len` readable bytes, or be null if `key_len` is zero.
+/// Output must be either null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ref_kind_map_move_ref(
+    map: *mut RefKindMap,
+    key: *const u8,
+    key_len: usize,
+    out: *mut *const c_void,
+) -> RefKindStatus {
+    // SAFETY: the caller upholds the requirements of the handle and the key
+    let (Some(map), Some(key)) = (unsafe { map.as_mut() }, unsafe { key_bytes(key, key_len) })
+    else {
+        return RefKindStatus::NullPointer;
+    };
+    if out.is_null() {
+        return RefKindStatus::NullPointer;
+    }
+    match map.try_move_ref(key) {
+        Ok(Some(pointer)) => {
+            // SAFETY: the caller guarantees that the output is valid for writes
+            unsafe { out.write(pointer) };
+            RefKindStatus::Ok
+        }
+        Ok(None) => RefKindStatus::NotFound,
+        Err(error) => error.into(),
+    }
+}
+
+/// Moves a mutable pointer out of the map by the key, writing it into `out`.
+///
+/// Nothing is written into `out` unless [`RefKindStatus::Ok`] is returned.
+///
+/// # Safety
+///
+/// Handle must be created by [`ref_kind_map_new`] and not destroyed yet.
+/// Key must point to `key_len` readable bytes, or be null if `key_len` is zero.
+/// Output must be either null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ref_kind_map_move_mut(
+    map: *mut RefKindMap,
+    key: *const u8,
+    key_len: usize,
+    out: *mut *mut c_void,
+) -> RefKindStatus {
+    // SAFETY: the caller upholds the requirements of the handle and the key
+    let (Some(map), Some(key)) = (unsafe { map.as_mut() }, unsafe { key_bytes(key, key_len) })
+    else {
+        return RefKindStatus::NullPointer;
+    };
+    if out.is_null() {
+        return RefKindStatus::NullPointer;
+    }
+    match map.try_move_mut(key) {
+        Ok(Some(pointer)) => {
+            // SAFETY: the caller guarantees that the output is valid for writes
+            unsafe { out.write(pointer) };
+            RefKindStatus::Ok
+        }
+        Ok(None) => RefKindStatus::NotFound,
+        Err(error) => error.into(),
+    }
+}
+
+/// Returns the bytes of the key, or [`None`] if the key is null while its length is not zero.
+///
+/// # Safety
+///
+/// Key must point to `len` readable bytes, or be null if `len` is zero.
+unsafe fn key_bytes<'k>(key: *const u8, len: usize) -> Option<&'k [u8]> {
+    if key.is_null() {
+        return (len == 0).then_some(&[]);
+    }
+    // SAFETY: the caller guarantees that the key points to `len` readable bytes
+    Some(unsafe { slice::from_raw_parts(key, len) })
+}
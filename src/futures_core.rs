@@ -0,0 +1,65 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_core::Stream;
+
+use crate::{MoveError, MoveResult, Mut, Ref, RefKind};
+
+/// Stream can be polled only through the mutable reference,
+/// so polling of [`Ref`] results in [`MoveError::BorrowedImmutably`] error.
+/// Note that such stream never ends, so consumers should stop polling on the first error.
+///
+/// ## Example
+///
+/// ```
+/// use core::{pin::Pin, task::{Context, Poll, Waker}};
+///
+/// use futures_core::Stream;
+/// use ref_kind::{MoveError, RefKind};
+///
+/// struct Countdown(u32);
+///
+/// impl Stream for Countdown {
+///     type Item = u32;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<u32>> {
+///         self.0 = self.0.saturating_sub(1);
+///         Poll::Ready((self.0 > 0).then_some(self.0))
+///     }
+/// }
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let mut countdown = Countdown(2);
+///
+/// let mut kind = RefKind::from(&mut countdown);
+/// assert_eq!(Pin::new(&mut kind).poll_next(&mut cx), Poll::Ready(Some(Ok(1))));
+/// assert_eq!(Pin::new(&mut kind).poll_next(&mut cx), Poll::Ready(None));
+///
+/// let mut kind = RefKind::from(&countdown);
+/// let error = Err(MoveError::BorrowedImmutably);
+/// assert_eq!(Pin::new(&mut kind).poll_next(&mut cx), Poll::Ready(Some(error)));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-core")))]
+impl<'a, S> Stream for RefKind<'a, S>
+where
+    S: ?Sized + Stream + Unpin + 'a,
+{
+    type Item = MoveResult<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut() {
+            Ref(_) => Poll::Ready(Some(Err(MoveError::BorrowedImmutably))),
+            Mut(unique) => Pin::new(&mut **unique)
+                .poll_next(cx)
+                .map(|item| item.map(Ok)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Ref(_) => (usize::MAX, None),
+            Mut(unique) => unique.size_hint(),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Provides export of the borrow state of collections for debugging.
+//!
+//! [`BorrowGraph`] describes the [availability](Availability) of every key of the collection
+//! and can be rendered into [DOT] format for graphviz or into JSON for custom tooling.
+//!
+//! [DOT]: https://graphviz.org/doc/info/lang.html
+
+use alloc_crate::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Debug, Write};
+
+#[cfg(feature = "debug-ledger")]
+use crate::sync::LedgerEntry;
+use crate::{Availability, RefKind, SparseSet};
+
+/// State of one key of the collection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BorrowNode {
+    /// Debug representation of the key.
+    pub key: String,
+    /// Availability of the reference stored by the key.
+    pub state: Availability,
+    /// Debug representation of the thread which claimed the reference, if known.
+    pub holder: Option<String>,
+}
+
+/// Description of the borrow state of every key of the collection.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{graph::BorrowGraph, Many, RefKind};
+///
+/// let mut numbers = [0, 1];
+/// let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+/// let _ = many.try_move_mut(1);
+///
+/// let graph = BorrowGraph::from_slice(&many);
+/// assert_eq!(
+///     graph.to_json(),
+///     r#"[{"key":"0","state":"mut","holder":null},{"key":"1","state":"moved","holder":null}]"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BorrowGraph {
+    nodes: Vec<BorrowNode>,
+}
+
+impl BorrowGraph {
+    /// Creates new empty graph.
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Creates new graph from the keys and optional [`RefKind`]s of the collection,
+    /// such as items of the iterator over `HashMap`.
+    pub fn from_entries<'s, 'a, K, T, I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, &'s Option<RefKind<'a, T>>)>,
+        K: Debug,
+        T: ?Sized + 'a,
+        'a: 's,
+    {
+        let mut graph = Self::new();
+        for (key, item) in entries {
+            graph.push(key, Availability::of(item));
+        }
+        graph
+    }
+
+    /// Creates new graph from the slice of optional [`RefKind`]s keyed by indices.
+    pub fn from_slice<T>(slice: &[Option<RefKind<'_, T>>]) -> Self
+    where
+        T: ?Sized,
+    {
+        Self::from_entries(slice.iter().enumerate())
+    }
+
+    /// Adds the state of the key into the graph.
+    pub fn push<K>(&mut self, key: K, state: Availability)
+    where
+        K: Debug,
+    {
+        let key = format!("{key:?}");
+        let node = BorrowNode {
+            key,
+            state,
+            holder: None,
+        };
+        self.nodes.push(node);
+    }
+
+    /// Returns states of all the keys of the graph.
+    pub fn nodes(&self) -> &[BorrowNode] {
+        &self.nodes
+    }
+
+    /// Fills holders of the claimed keys from the records of the ledger.
+    ///
+    /// Holder of the key is the thread of the first successful move by this key.
+    #[cfg(feature = "debug-ledger")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "debug-ledger")))]
+    pub fn with_ledger<'l, I>(mut self, ledger: I) -> Self
+    where
+        I: IntoIterator<Item = &'l LedgerEntry>,
+    {
+        let ledger: Vec<_> = ledger
+            .into_iter()
+            .filter(|entry| entry.outcome.is_ok())
+            .collect();
+        for node in &mut self.nodes {
+            if node.state == Availability::Mut {
+                continue;
+            }
+            node.holder = ledger
+                .iter()
+                .find(|entry| entry.key == node.key)
+                .map(|entry| format!("{:?}", entry.thread));
+        }
+        self
+    }
+
+    /// Renders the graph into [DOT](https://graphviz.org/doc/info/lang.html) format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph borrows {\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = Escaped(&node.key);
+            let state = state_name(node.state);
+            let _ = writeln!(dot, "    n{index} [label=\"{label}: {state}\"];");
+            if let Some(holder) = &node.holder {
+                let holder = Escaped(holder);
+                let _ = writeln!(dot, "    \"{holder}\" [shape=box];");
+                let _ = writeln!(dot, "    \"{holder}\" -> n{index};");
+            }
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Renders the graph into JSON array of objects with `key`, `state` and `holder` fields.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (index, node) in self.nodes.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let key = Escaped(&node.key);
+            let state = state_name(node.state);
+            let holder = match &node.holder {
+                Some(holder) => format!("\"{}\"", Escaped(holder)),
+                None => "null".to_string(),
+            };
+            let _ = write!(
+                json,
+                r#"{{"key":"{key}","state":"{state}","holder":{holder}}}"#
+            );
+        }
+        json.push(']');
+        json
+    }
+}
+
+impl<'a, T> SparseSet<Option<RefKind<'a, T>>>
+where
+    T: ?Sized + 'a,
+{
+    /// Returns the borrow state of every key of the set.
+    #[cfg_attr(docsrs, doc(cfg(feature = "debug-graph")))]
+    pub fn borrow_graph(&self) -> BorrowGraph {
+        BorrowGraph::from_entries(self.iter())
+    }
+}
+
+fn state_name(state: Availability) -> &'static str {
+    match state {
+        Availability::Mut => "mut",
+        Availability::Ref => "ref",
+        Availability::Moved => "moved",
+    }
+}
+
+/// Escapes quotes, backslashes and control characters of the string.
+struct Escaped<'s>(&'s str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for char in self.0.chars() {
+            match char {
+                '"' | '\\' => write!(f, "\\{char}")?,
+                '\n' => f.write_str("\\n")?,
+                char if char.is_control() => write!(f, "\\u{:04x}", char as u32)?,
+                char => f.write_char(char)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+use core::array;
+#[cfg(feature = "alloc")]
+use core::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
+
+#[cfg(feature = "alloc")]
+use alloc_crate::vec::Vec;
+
+use crate::{Many, MoveResult};
+
+/// Trait for keys which can be used to move references out of [slices](prim@slice).
+///
+/// This trait mirrors [`SliceIndex`](core::slice::SliceIndex) of the standard library:
+/// implementations of [`Many`] trait for slices, arrays and vectors accept any key
+/// which implements this trait, and the type of key defines the type of the output.
+///
+/// | Key type                              | Output type             | Out of bounds     |
+/// |---------------------------------------|-------------------------|-------------------|
+/// | `usize` or other [`KeyIndex`]         | `Option<T::Ref>`        | [`None`]          |
+/// | `[K; N]` where `K` is [`KeyIndex`]    | `[Option<T::Ref>; N]`   | [`None`] per key  |
+/// | [`FromEnd`]                           | `Option<T::Ref>`        | [`None`]          |
+/// | ranges (`a..b`, `a..=b`, `..` etc.)   | `Option<Vec<T::Ref>>`   | [`None`]          |
+///
+/// Keys of arrays and ranges are moved one by one in order,
+/// so if some of them fails, references moved before are not returned into the collection.
+///
+/// ## Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use ref_kind::{Many, MoveError, RefKind};
+///
+/// let mut array = [0, 1, 2, 3, 4];
+/// let mut many: Vec<_> = array.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+///
+/// let [zero, two] = many.try_move_mut([0, 2]).unwrap();
+/// assert_eq!((zero, two), (Some(&mut 0), Some(&mut 2)));
+///
+/// let rest = many.try_move_ref(3..).unwrap();
+/// assert_eq!(rest, Some(vec![&3, &4]));
+///
+/// let error = many.try_move_mut(1..3);
+/// assert_eq!(error, Err(MoveError::BorrowedMutably));
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+pub trait ManyIndex<'a, T>: Sized {
+    /// The type of a reference which is being moved out.
+    type Ref: 'a;
+
+    /// Tries to move an immutable reference out of the slice by this key.
+    fn try_move_ref(self, slice: &mut [T]) -> MoveResult<Self::Ref>;
+
+    /// The type of a mutable reference which is being moved out.
+    type Mut: 'a;
+
+    /// Tries to move a mutable reference out of the slice by this key.
+    fn try_move_mut(self, slice: &mut [T]) -> MoveResult<Self::Mut>;
+}
+
+/// Trait for compact or typed keys which can be converted into an index of [slice](prim@slice).
+///
+/// Any type which implements this trait can be used as a key of slices, arrays and vectors
+/// (alone or in arrays of keys), so there is no need to cast keys to `usize` at call sites.
+/// Elements are moved out by the key itself, so nested collections receive the key of the same type.
+///
+/// Of the primitive types, this trait is implemented for `usize` only:
+/// otherwise integer literals used as keys could not be inferred to be `usize`.
+/// Compact keys such as `u8` or `u16` should be wrapped into newtypes.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{KeyIndex, Many, RefKind};
+///
+/// #[derive(Debug, Clone, Copy)]
+/// struct Entity(u16);
+///
+/// impl KeyIndex for Entity {
+///     fn index(self) -> usize {
+///         self.0.into()
+///     }
+/// }
+///
+/// let mut positions = [(0, 0), (1, 1), (2, 2)];
+/// let mut many = positions.each_mut().map(|i| Some(RefKind::from(i)));
+///
+/// let [first, last] = many.try_move_mut([Entity(0), Entity(2)]).unwrap();
+/// assert_eq!((first, last), (Some(&mut (0, 0)), Some(&mut (2, 2))));
+/// assert_eq!(many.try_move_ref(Entity(1)), Ok(Some(&(1, 1))));
+/// assert_eq!(many.try_move_ref(3), Ok(None));
+/// ```
+pub trait KeyIndex: Copy {
+    /// Converts this key into an index of the slice.
+    fn index(self) -> usize;
+}
+
+impl KeyIndex for usize {
+    fn index(self) -> usize {
+        self
+    }
+}
+
+impl<'a, T, K> ManyIndex<'a, T> for K
+where
+    K: KeyIndex,
+    T: Many<'a, K>,
+{
+    type Ref = Option<T::Ref>;
+
+    fn try_move_ref(self, slice: &mut [T]) -> MoveResult<Self::Ref> {
+        let item = match slice.get_mut(self.index()) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let shared = item.try_move_ref(self)?;
+        Ok(Some(shared))
+    }
+
+    type Mut = Option<T::Mut>;
+
+    fn try_move_mut(self, slice: &mut [T]) -> MoveResult<Self::Mut> {
+        let item = match slice.get_mut(self.index()) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let unique = item.try_move_mut(self)?;
+        Ok(Some(unique))
+    }
+}
+
+impl<'a, T, K, const N: usize> ManyIndex<'a, T> for [K; N]
+where
+    K: KeyIndex,
+    T: Many<'a, K>,
+{
+    type Ref = [Option<T::Ref>; N];
+
+    fn try_move_ref(self, slice: &mut [T]) -> MoveResult<Self::Ref> {
+        try_from_fn(|i| self[i].try_move_ref(slice))
+    }
+
+    type Mut = [Option<T::Mut>; N];
+
+    fn try_move_mut(self, slice: &mut [T]) -> MoveResult<Self::Mut> {
+        try_from_fn(|i| self[i].try_move_mut(slice))
+    }
+}
+
+/// Key which addresses an element of [slice](prim@slice) relative to its end.
+///
+/// `FromEnd(1)` addresses the last element, `FromEnd(2)` the one before it, and so on.
+/// Key is resolved against the length of the slice at the time of the move,
+/// so it keeps addressing the latest elements after the collection was truncated or grown.
+/// Nested collections receive the resolved index.
+///
+/// ## Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use ref_kind::{FromEnd, Many, RefKind};
+///
+/// let mut samples = [1, 2, 3, 4];
+/// let mut many: Vec<_> = samples.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+///
+/// assert_eq!(many.try_move_mut(FromEnd(1)), Ok(Some(&mut 4)));
+/// many.truncate(2);
+/// assert_eq!(many.try_move_ref(FromEnd(1)), Ok(Some(&2)));
+/// assert_eq!(many.try_move_ref(FromEnd(3)), Ok(None));
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FromEnd(pub usize);
+
+impl FromEnd {
+    fn resolve(self, len: usize) -> Option<usize> {
+        len.checked_sub(self.0).filter(|_| self.0 > 0)
+    }
+}
+
+impl<'a, T> ManyIndex<'a, T> for FromEnd
+where
+    T: Many<'a, usize>,
+{
+    type Ref = Option<T::Ref>;
+
+    fn try_move_ref(self, slice: &mut [T]) -> MoveResult<Self::Ref> {
+        let index = match self.resolve(slice.len()) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let shared = slice[index].try_move_ref(index)?;
+        Ok(Some(shared))
+    }
+
+    type Mut = Option<T::Mut>;
+
+    fn try_move_mut(self, slice: &mut [T]) -> MoveResult<Self::Mut> {
+        let index = match self.resolve(slice.len()) {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let unique = slice[index].try_move_mut(index)?;
+        Ok(Some(unique))
+    }
+}
+
+fn try_from_fn<T, F, const N: usize>(mut f: F) -> MoveResult<[Option<T>; N]>
+where
+    F: FnMut(usize) -> MoveResult<Option<T>>,
+{
+    let mut result = Ok(());
+    let array = array::from_fn(|i| match result {
+        Ok(()) => f(i).unwrap_or_else(|error| {
+            result = Err(error);
+            None
+        }),
+        Err(_) => None,
+    });
+    result.map(|()| array)
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_range {
+    ($($range:ty),* $(,)?) => {$(
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        impl<'a, T> ManyIndex<'a, T> for $range
+        where
+            T: Many<'a, usize>,
+        {
+            type Ref = Option<Vec<T::Ref>>;
+
+            fn try_move_ref(self, slice: &mut [T]) -> MoveResult<Self::Ref> {
+                let range = match into_range(self, slice.len()) {
+                    Some(range) => range,
+                    None => return Ok(None),
+                };
+                let shared = range
+                    .map(|key| slice[key].try_move_ref(key))
+                    .collect::<MoveResult<_>>()?;
+                Ok(Some(shared))
+            }
+
+            type Mut = Option<Vec<T::Mut>>;
+
+            fn try_move_mut(self, slice: &mut [T]) -> MoveResult<Self::Mut> {
+                let range = match into_range(self, slice.len()) {
+                    Some(range) => range,
+                    None => return Ok(None),
+                };
+                let unique = range
+                    .map(|key| slice[key].try_move_mut(key))
+                    .collect::<MoveResult<_>>()?;
+                Ok(Some(unique))
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "alloc")]
+impl_range!(
+    Range<usize>,
+    RangeInclusive<usize>,
+    RangeFrom<usize>,
+    RangeTo<usize>,
+    RangeToInclusive<usize>,
+    RangeFull,
+    (Bound<usize>, Bound<usize>),
+);
+
+#[cfg(feature = "alloc")]
+fn into_range<R>(range: R, len: usize) -> Option<Range<usize>>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    (start <= end && end <= len).then_some(start..end)
+}
@@ -0,0 +1,298 @@
+use core::hash::{BuildHasher, Hash};
+use std_crate::hash::RandomState;
+
+use indexmap::IndexMap;
+
+use crate::{Availability, Many, ManyAvailability, MoveResult, RefKind};
+
+/// Insertion-ordered map of optional [`RefKind`]s.
+///
+/// Entries keep the order in which they were inserted, so iteration over the map is deterministic.
+/// This is useful for order-stable registries, such as z-ordered widgets or replays.
+/// Besides moving references out by the key, it allows to move them out by the position of the entry.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Many, MoveError, RefKind, RefKindIndexMap};
+///
+/// let mut layers = [0, 1, 2];
+/// let mut map: RefKindIndexMap<_, _> = ["background", "scene", "overlay"]
+///     .into_iter()
+///     .zip(layers.iter_mut().map(RefKind::from))
+///     .collect();
+///
+/// let top = map.try_move_mut_at(map.len() - 1).unwrap().unwrap();
+/// *top += 10;
+/// assert_eq!(map.try_move_ref("overlay"), Err(MoveError::BorrowedMutably));
+/// assert_eq!(map.try_move_ref_at(0), Ok(Some(&0)));
+///
+/// let order: Vec<_> = map.get_ref().keys().copied().collect();
+/// assert_eq!(order, ["background", "scene", "overlay"]);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+#[derive(Debug)]
+pub struct RefKindIndexMap<'a, K, T, S = RandomState>
+where
+    T: ?Sized,
+{
+    map: IndexMap<K, Option<RefKind<'a, T>>, S>,
+}
+
+impl<'a, K, T> RefKindIndexMap<'a, K, T>
+where
+    T: ?Sized,
+{
+    /// Creates new empty map.
+    pub fn new() -> Self {
+        let map = IndexMap::new();
+        Self { map }
+    }
+}
+
+impl<'a, K, T, S> RefKindIndexMap<'a, K, T, S>
+where
+    T: ?Sized,
+{
+    /// Creates new empty map which will use the given hash builder to hash keys.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        let map = IndexMap::with_hasher(hash_builder);
+        Self { map }
+    }
+
+    /// Returns the count of entries in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the count of bytes allocated on the heap by the map.
+    ///
+    /// This is an estimation based on the capacity of the map:
+    /// every entry stores its hash, key and kind of reference,
+    /// and the hash table stores an index of the entry and one control byte.
+    /// Heap memory owned by the keys themselves is not included.
+    pub fn allocated_bytes(&self) -> usize {
+        let entry = size_of::<usize>() + size_of::<K>() + size_of::<Option<RefKind<'a, T>>>();
+        let index = size_of::<usize>() + 1;
+        self.map.capacity() * (entry + index)
+    }
+
+    /// Returns an immutable reference to the underlying map.
+    pub fn get_ref(&self) -> &IndexMap<K, Option<RefKind<'a, T>>, S> {
+        &self.map
+    }
+
+    /// Returns the underlying map, consuming the `self` value.
+    pub fn into_inner(self) -> IndexMap<K, Option<RefKind<'a, T>>, S> {
+        self.map
+    }
+
+    /// Tries to move an immutable reference out of the entry at the position in the order of insertion.
+    ///
+    /// Returns [`None`] if the position is out of bounds.
+    pub fn try_move_ref_at(&mut self, index: usize) -> MoveResult<Option<&'a T>> {
+        let item = match self.map.get_index_mut(index) {
+            Some((_, item)) => item,
+            None => return Ok(None),
+        };
+        let shared = item.try_move_ref(index)?;
+        Ok(Some(shared))
+    }
+
+    /// Moves an immutable reference out of the entry at the position in the order of insertion.
+    ///
+    /// Returns [`None`] if the position is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the entry.
+    #[track_caller]
+    pub fn move_ref_at(&mut self, index: usize) -> Option<&'a T> {
+        match self.try_move_ref_at(index) {
+            Ok(result) => result,
+            Err(error) => crate::many::move_keyed_panic(error, &index),
+        }
+    }
+
+    /// Tries to move a mutable reference out of the entry at the position in the order of insertion.
+    ///
+    /// Returns [`None`] if the position is out of bounds.
+    pub fn try_move_mut_at(&mut self, index: usize) -> MoveResult<Option<&'a mut T>> {
+        let item = match self.map.get_index_mut(index) {
+            Some((_, item)) => item,
+            None => return Ok(None),
+        };
+        let unique = item.try_move_mut(index)?;
+        Ok(Some(unique))
+    }
+
+    /// Moves a mutable reference out of the entry at the position in the order of insertion.
+    ///
+    /// Returns [`None`] if the position is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the entry
+    /// or the value was already borrowed as immutable.
+    #[track_caller]
+    pub fn move_mut_at(&mut self, index: usize) -> Option<&'a mut T> {
+        match self.try_move_mut_at(index) {
+            Ok(result) => result,
+            Err(error) => crate::many::move_keyed_panic(error, &index),
+        }
+    }
+}
+
+impl<'a, K, T, S> RefKindIndexMap<'a, K, T, S>
+where
+    K: Hash + Eq,
+    T: ?Sized,
+    S: BuildHasher,
+{
+    /// Checks if the map contains an entry by the key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the position of the entry by the key in the order of insertion.
+    pub fn get_index_of(&self, key: &K) -> Option<usize> {
+        self.map.get_index_of(key)
+    }
+
+    /// Inserts the reference by the key, returning the reference which was stored before, if any.
+    ///
+    /// New keys are appended to the end of the map,
+    /// while existing keys keep their position.
+    pub fn insert(
+        &mut self,
+        key: K,
+        value: Option<RefKind<'a, T>>,
+    ) -> Option<Option<RefKind<'a, T>>> {
+        self.map.insert(key, value)
+    }
+
+    /// Removes the entry by the key, returning its reference if the entry was stored in the map.
+    ///
+    /// Entries after the removed one are shifted, preserving the order of insertion.
+    pub fn shift_remove(&mut self, key: &K) -> Option<Option<RefKind<'a, T>>> {
+        self.map.shift_remove(key)
+    }
+}
+
+impl<'a, K, T> Default for RefKindIndexMap<'a, K, T>
+where
+    T: ?Sized,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, K, T, S> From<IndexMap<K, Option<RefKind<'a, T>>, S>> for RefKindIndexMap<'a, K, T, S>
+where
+    T: ?Sized,
+{
+    fn from(map: IndexMap<K, Option<RefKind<'a, T>>, S>) -> Self {
+        Self { map }
+    }
+}
+
+impl<'a, K, T> FromIterator<(K, RefKind<'a, T>)> for RefKindIndexMap<'a, K, T>
+where
+    K: Hash + Eq,
+    T: ?Sized,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, RefKind<'a, T>)>,
+    {
+        let map = iter
+            .into_iter()
+            .map(|(key, kind)| (key, Some(kind)))
+            .collect();
+        Self { map }
+    }
+}
+
+/// Implementation of [`Many`] trait for [`RefKindIndexMap`].
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+impl<'a, K, T, S> Many<'a, K> for RefKindIndexMap<'a, K, T, S>
+where
+    K: Hash + Eq,
+    T: ?Sized + 'a,
+    S: BuildHasher,
+{
+    type Ref = Option<&'a T>;
+
+    fn try_move_ref(&mut self, key: K) -> MoveResult<Self::Ref> {
+        self.map.try_move_ref(key)
+    }
+
+    type Mut = Option<&'a mut T>;
+
+    fn try_move_mut(&mut self, key: K) -> MoveResult<Self::Mut> {
+        self.map.try_move_mut(key)
+    }
+}
+
+/// Implementation of [`Many`] trait for [`indexmap::IndexMap`].
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+impl<'a, K, V, S> Many<'a, K> for IndexMap<K, V, S>
+where
+    K: Hash + Eq,
+    V: Many<'a, K>,
+    S: BuildHasher,
+{
+    type Ref = Option<V::Ref>;
+
+    fn try_move_ref(&mut self, key: K) -> MoveResult<Self::Ref> {
+        let item = match self.get_mut(&key) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let shared = item.try_move_ref(key)?;
+        Ok(Some(shared))
+    }
+
+    type Mut = Option<V::Mut>;
+
+    fn try_move_mut(&mut self, key: K) -> MoveResult<Self::Mut> {
+        let item = match self.get_mut(&key) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let unique = item.try_move_mut(key)?;
+        Ok(Some(unique))
+    }
+}
+
+/// Implementation of [`ManyAvailability`] trait for [`RefKindIndexMap`].
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+impl<K, T, S> ManyAvailability<K> for RefKindIndexMap<'_, K, T, S>
+where
+    K: Hash + Eq,
+    T: ?Sized,
+    S: BuildHasher,
+{
+    fn availability(&self, key: &K) -> Option<Availability> {
+        self.map.availability(key)
+    }
+}
+
+/// Implementation of [`ManyAvailability`] trait for [`indexmap::IndexMap`].
+#[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
+impl<K, V, S> ManyAvailability<K> for IndexMap<K, V, S>
+where
+    K: Hash + Eq,
+    V: ManyAvailability<K>,
+    S: BuildHasher,
+{
+    fn availability(&self, key: &K) -> Option<Availability> {
+        self.get(key)?.availability(key)
+    }
+}
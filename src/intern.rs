@@ -0,0 +1,152 @@
+//! Provides interned symbols which can be used as cheap keys of string-keyed collections.
+//!
+//! Moving a reference out of a collection keyed by `String` requires an owned key,
+//! so the name is cloned and hashed in full on every move.
+//! [`Symbol`] is a copyable key which is hashed as an integer,
+//! and [`SymbolTable`] allows to move references out by the name without allocations.
+
+use core::fmt;
+use std_crate::{collections::HashMap, sync::Arc, vec::Vec};
+
+use crate::{KeyIndex, Many, MoveResult};
+
+/// Interned name which can be used as a key of collections.
+///
+/// Symbols are created by the [`SymbolTable`] in the order of interning starting from zero,
+/// so they can be used as [indices](KeyIndex) of dense collections too.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl KeyIndex for Symbol {
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Table of interned names.
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use ref_kind::{intern::SymbolTable, RefKind};
+///
+/// let (mut width, height) = (800, 600);
+/// let mut symbols = SymbolTable::new();
+/// let mut many = HashMap::from([
+///     (symbols.intern("width"), Some(RefKind::from(&mut width))),
+///     (symbols.intern("height"), Some(RefKind::from(&height))),
+/// ]);
+///
+/// let width = symbols.try_move_mut(&mut many, "width").unwrap();
+/// assert_eq!(width, Some(Some(&mut 800)));
+/// let height = symbols.try_move_ref(&mut many, "height").unwrap();
+/// assert_eq!(height, Some(Some(&600)));
+/// // Names which were never interned are not present in any collection
+/// assert_eq!(symbols.try_move_ref(&mut many, "depth"), Ok(None));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: HashMap<Arc<str>, Symbol>,
+    names: Vec<Arc<str>>,
+}
+
+impl SymbolTable {
+    /// Creates new empty symbol table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of interned names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Checks if no names were interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Interns the name, returning its symbol.
+    ///
+    /// The name is allocated only once, when it is interned for the first time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of interned names overflows `u32`.
+    /// See [`try_intern`](SymbolTable::try_intern) for the fallible version.
+    #[track_caller]
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        match self.try_intern(name) {
+            Ok(symbol) => symbol,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Tries to intern the name, returning its symbol.
+    ///
+    /// The name is allocated only once, when it is interned for the first time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of interned names overflows `u32`.
+    pub fn try_intern(&mut self, name: &str) -> Result<Symbol, InternError> {
+        if let Some(&symbol) = self.symbols.get(name) {
+            return Ok(symbol);
+        }
+        let symbol = u32::try_from(self.names.len())
+            .map(Symbol)
+            .map_err(|_| InternError(()))?;
+        let name: Arc<str> = name.into();
+        self.names.push(Arc::clone(&name));
+        self.symbols.insert(name, symbol);
+        Ok(symbol)
+    }
+
+    /// Returns the symbol of the name if it was interned.
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// Returns the name of the symbol if it was interned by this table.
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.names.get(symbol.index()).map(AsRef::as_ref)
+    }
+
+    /// Tries to move an immutable reference out of the collection by the name.
+    ///
+    /// Returns [`None`] if the name was never interned by this table.
+    pub fn try_move_ref<'a, M>(&self, many: &mut M, name: &str) -> MoveResult<Option<M::Ref>>
+    where
+        M: ?Sized + Many<'a, Symbol>,
+    {
+        self.get(name)
+            .map(|symbol| many.try_move_ref(symbol))
+            .transpose()
+    }
+
+    /// Tries to move a mutable reference out of the collection by the name.
+    ///
+    /// Returns [`None`] if the name was never interned by this table.
+    pub fn try_move_mut<'a, M>(&self, many: &mut M, name: &str) -> MoveResult<Option<M::Mut>>
+    where
+        M: ?Sized + Many<'a, Symbol>,
+    {
+        self.get(name)
+            .map(|symbol| many.try_move_mut(symbol))
+            .transpose()
+    }
+}
+
+/// Error which occurs when the symbol table cannot intern more names.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct InternError(());
+
+impl fmt::Display for InternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "too many interned names")
+    }
+}
+
+impl std_crate::error::Error for InternError {}
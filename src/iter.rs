@@ -3,7 +3,7 @@
 
 use core::iter::Peekable;
 
-use crate::{Many, Result};
+use crate::{Many, MoveResult};
 
 /// Type of key for peekable iterator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -56,14 +56,14 @@ where
 {
     type Ref = Option<Item::Ref>;
 
-    fn try_move_ref(&mut self, key: PeekableKey<Key>) -> Result<Self::Ref> {
+    fn try_move_ref(&mut self, key: PeekableKey<Key>) -> MoveResult<Self::Ref> {
         let (key, item) = peek_by_key(self, key);
         item.map(|item| item.try_move_ref(key)).transpose()
     }
 
     type Mut = Option<Item::Mut>;
 
-    fn try_move_mut(&mut self, key: PeekableKey<Key>) -> Result<Self::Mut> {
+    fn try_move_mut(&mut self, key: PeekableKey<Key>) -> MoveResult<Self::Mut> {
         let (key, item) = peek_by_key(self, key);
         item.map(|item| item.try_move_mut(key)).transpose()
     }
@@ -0,0 +1,76 @@
+use crate::{Many, MoveResult};
+
+/// Wrapper of the collection which maps keys with the closure before moving references out.
+///
+/// Ordered collections of the standard library cannot be parameterized by a custom comparator,
+/// so keys are usually normalized before insertion instead (e.g. lowercased for case-insensitive lookups).
+/// This wrapper applies the same normalization to every key at the call site,
+/// so callers do not need to wrap or convert keys by themselves.
+///
+/// ## Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use std::collections::BTreeMap;
+///
+/// use ref_kind::{KeyMapped, Many, RefKind};
+///
+/// let mut volume = 50;
+/// let many = BTreeMap::from([("volume".to_owned(), Some(RefKind::from(&mut volume)))]);
+/// let mut many = KeyMapped::new(many, |key: &str| key.to_lowercase());
+///
+/// assert_eq!(many.try_move_mut("Volume"), Ok(Some(&mut 50)));
+/// assert_eq!(many.try_move_ref("width"), Ok(None));
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KeyMapped<M, F> {
+    many: M,
+    f: F,
+}
+
+impl<M, F> KeyMapped<M, F> {
+    /// Creates new wrapper of the collection which maps keys with the closure.
+    pub const fn new(many: M, f: F) -> Self {
+        Self { many, f }
+    }
+
+    /// Returns an immutable reference to the underlying collection.
+    pub fn get_ref(&self) -> &M {
+        &self.many
+    }
+
+    /// Returns a mutable reference to the underlying collection.
+    pub fn get_mut(&mut self) -> &mut M {
+        &mut self.many
+    }
+
+    /// Returns the underlying collection, consuming the `self` value.
+    pub fn into_inner(self) -> M {
+        self.many
+    }
+}
+
+/// Implementation of [`Many`] trait for [`KeyMapped`] which moves references out by the mapped keys.
+impl<'a, M, F, Key, Mapped> Many<'a, Key> for KeyMapped<M, F>
+where
+    M: Many<'a, Mapped>,
+    F: FnMut(Key) -> Mapped,
+{
+    type Ref = M::Ref;
+
+    fn try_move_ref(&mut self, key: Key) -> MoveResult<Self::Ref> {
+        let key = (self.f)(key);
+        self.many.try_move_ref(key)
+    }
+
+    type Mut = M::Mut;
+
+    fn try_move_mut(&mut self, key: Key) -> MoveResult<Self::Mut> {
+        let key = (self.f)(key);
+        self.many.try_move_mut(key)
+    }
+}
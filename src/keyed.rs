@@ -0,0 +1,129 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{KeyIndex, Many, MoveResult};
+
+/// Wrapper of the dense collection which accepts keys of one [typed key](KeyIndex) only.
+///
+/// Dense collections such as slices, vectors or sparse sets
+/// accept any `usize` index, so indices of different registries can be mixed up by accident.
+/// This wrapper implements [`Many`] trait for its key type only,
+/// so such mistakes are caught at compile time.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{KeyIndex, Keyed, Many, RefKind};
+///
+/// #[derive(Debug, Clone, Copy)]
+/// struct EntityId(u32);
+///
+/// impl KeyIndex for EntityId {
+///     fn index(self) -> usize {
+///         self.0 as usize
+///     }
+/// }
+///
+/// let mut positions = [(0, 0), (1, 1), (2, 2)];
+/// let many = positions.each_mut().map(|i| Some(RefKind::from(i)));
+/// let mut many = Keyed::<EntityId, _>::new(many);
+///
+/// assert_eq!(many.try_move_mut(EntityId(2)), Ok(Some(&mut (2, 2))));
+/// assert_eq!(many.try_move_ref(EntityId(3)), Ok(None));
+/// ```
+///
+/// Plain indices are not accepted:
+///
+/// ```compile_fail
+/// # use ref_kind::{KeyIndex, Keyed, Many, RefKind};
+/// # #[derive(Debug, Clone, Copy)]
+/// # struct EntityId(u32);
+/// # impl KeyIndex for EntityId {
+/// #     fn index(self) -> usize {
+/// #         self.0 as usize
+/// #     }
+/// # }
+/// let mut positions = [(0, 0), (1, 1), (2, 2)];
+/// let many = positions.each_mut().map(|i| Some(RefKind::from(i)));
+/// let mut many = Keyed::<EntityId, _>::new(many);
+///
+/// let _ = many.try_move_mut(2_usize);
+/// ```
+pub struct Keyed<K, M> {
+    many: M,
+    key: PhantomData<fn(K)>,
+}
+
+impl<K, M> Keyed<K, M> {
+    /// Creates new wrapper of the collection which accepts keys of type `K`.
+    pub const fn new(many: M) -> Self {
+        let key = PhantomData;
+        Self { many, key }
+    }
+
+    /// Returns an immutable reference to the underlying collection.
+    pub fn get_ref(&self) -> &M {
+        &self.many
+    }
+
+    /// Returns a mutable reference to the underlying collection.
+    pub fn get_mut(&mut self) -> &mut M {
+        &mut self.many
+    }
+
+    /// Returns the underlying collection, consuming the `self` value.
+    pub fn into_inner(self) -> M {
+        self.many
+    }
+}
+
+impl<K, M> fmt::Debug for Keyed<K, M>
+where
+    M: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Keyed").field(&self.many).finish()
+    }
+}
+
+impl<K, M> Clone for Keyed<K, M>
+where
+    M: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.many.clone())
+    }
+}
+
+impl<K, M> Default for Keyed<K, M>
+where
+    M: Default,
+{
+    fn default() -> Self {
+        Self::new(M::default())
+    }
+}
+
+impl<K, M> From<M> for Keyed<K, M> {
+    fn from(many: M) -> Self {
+        Self::new(many)
+    }
+}
+
+/// Implementation of [`Many`] trait for [`Keyed`] which converts typed keys into indices.
+impl<'a, K, M> Many<'a, K> for Keyed<K, M>
+where
+    K: KeyIndex,
+    M: Many<'a, usize>,
+{
+    type Ref = M::Ref;
+
+    fn try_move_ref(&mut self, key: K) -> MoveResult<Self::Ref> {
+        self.many.try_move_ref(key.index())
+    }
+
+    type Mut = M::Mut;
+
+    fn try_move_mut(&mut self, key: K) -> MoveResult<Self::Mut> {
+        self.many.try_move_mut(key.index())
+    }
+}
@@ -1,4 +1,11 @@
-use core::ops::Deref;
+use core::{
+    future::Future,
+    ops::{Deref, DerefMut, Index},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{MoveError, MoveResult};
 
 use self::RefKind::{Mut, Ref};
 
@@ -21,26 +28,29 @@ where
 {
     /// Checks if [`RefKind`] contains immutable reference.
     #[inline]
-    pub fn is_ref(&self) -> bool {
+    pub const fn is_ref(&self) -> bool {
         matches!(self, Ref(_))
     }
 
     /// Checks if [`RefKind`] contains mutable reference.
     #[inline]
-    pub fn is_mut(&self) -> bool {
+    pub const fn is_mut(&self) -> bool {
         matches!(self, Mut(_))
     }
 
     /// Returns an immutable reference from the [`RefKind`].
     #[inline]
-    pub fn get_ref(&self) -> &T {
-        self
+    pub const fn get_ref(&self) -> &T {
+        match self {
+            Ref(shared) => shared,
+            Mut(unique) => unique,
+        }
     }
 
     /// Returns [`Some`] with a mutable reference from the struct
     /// or [`None`] if contained reference is immutable.
     #[inline]
-    pub fn get_mut(&mut self) -> Option<&mut T> {
+    pub const fn get_mut(&mut self) -> Option<&mut T> {
         match self {
             Ref(_) => None,
             Mut(unique) => Some(unique),
@@ -50,7 +60,7 @@ where
     /// Converts [`RefKind`] into immutable reference with the lifetime of the owner,
     /// consuming the `self` value.
     #[inline]
-    pub fn into_ref(self) -> &'a T {
+    pub const fn into_ref(self) -> &'a T {
         match self {
             Ref(shared) => shared,
             Mut(unique) => unique,
@@ -59,14 +69,232 @@ where
 
     /// Returns [`Some`] with a mutable reference with the lifetime of the owner
     /// or [`None`] if contained reference is immutable, consuming the `self` value.
+    ///
+    /// Immutable reference is discarded in the latter case:
+    /// use [`try_into_mut`](RefKind::try_into_mut) to get it back.
     #[inline]
-    pub fn into_mut(self) -> Option<&'a mut T> {
+    pub const fn into_mut(self) -> Option<&'a mut T> {
         match self {
             Ref(_) => None,
             Mut(unique) => Some(unique),
         }
     }
 
+    /// Converts [`RefKind`] into mutable reference with the lifetime of the owner,
+    /// consuming the `self` value.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `self` value back if contained reference is immutable.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::RefKind;
+    ///
+    /// let number = 42;
+    /// let kind = RefKind::from(&number);
+    ///
+    /// let kind = kind.try_into_mut().unwrap_err();
+    /// assert_eq!(kind.into_ref(), &42);
+    /// ```
+    #[inline]
+    pub const fn try_into_mut(self) -> Result<&'a mut T, Self> {
+        match self {
+            Ref(shared) => Err(Ref(shared)),
+            Mut(unique) => Ok(unique),
+        }
+    }
+
+    /// Maps the contained reference to the reference of another type (e.g. to the field of the value),
+    /// preserving the kind of reference, consuming the `self` value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::RefKind;
+    ///
+    /// let mut position = (1, 2);
+    /// let x = RefKind::from(&mut position).map(|p| &p.0, |p| &mut p.0);
+    /// assert_eq!(x, RefKind::Mut(&mut 1));
+    /// ```
+    #[inline]
+    pub fn map<U, R, M>(self, f_ref: R, f_mut: M) -> RefKind<'a, U>
+    where
+        U: ?Sized + 'a,
+        R: FnOnce(&'a T) -> &'a U,
+        M: FnOnce(&'a mut T) -> &'a mut U,
+    {
+        match self {
+            Ref(shared) => Ref(f_ref(shared)),
+            Mut(unique) => Mut(f_mut(unique)),
+        }
+    }
+
+    /// Reborrows the contained reference for the shorter lifetime, preserving the kind of reference.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::RefKind;
+    ///
+    /// let mut number = 42;
+    /// let mut kind = RefKind::from(&mut number);
+    ///
+    /// if let Some(number) = kind.reborrow().into_mut() {
+    ///     *number += 1;
+    /// }
+    /// assert_eq!(kind.into_mut(), Some(&mut 43));
+    /// ```
+    #[inline]
+    pub fn reborrow(&mut self) -> RefKind<'_, T> {
+        match self {
+            Ref(shared) => Ref(shared),
+            Mut(unique) => Mut(unique),
+        }
+    }
+
+    /// Converts [`RefKind`] of the smart pointer into [`RefKind`] of its target,
+    /// preserving the kind of reference, consuming the `self` value.
+    ///
+    /// Use [`try_into_deref`](RefKind::try_into_deref) for smart pointers
+    /// which implement [`Deref`] only, such as [`Rc`](https://doc.rust-lang.org/std/rc/struct.Rc.html).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::RefKind;
+    ///
+    /// let mut name = String::from("player");
+    /// let kind: RefKind<str> = RefKind::from(&mut name).into_deref();
+    /// assert!(kind.is_mut());
+    /// assert_eq!(&*kind, "player");
+    /// ```
+    #[inline]
+    pub fn into_deref(self) -> RefKind<'a, T::Target>
+    where
+        T: DerefMut,
+    {
+        match self {
+            Ref(shared) => Ref(shared),
+            Mut(unique) => Mut(unique),
+        }
+    }
+
+    /// Tries to convert [`RefKind`] of the smart pointer into [`RefKind`] of its target,
+    /// consuming the `self` value.
+    ///
+    /// Only [`Deref`] is required from the smart pointer,
+    /// so the [`Mut`] value cannot be converted and is returned back as an error.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    ///
+    /// use ref_kind::RefKind;
+    ///
+    /// let shared = Rc::new(42);
+    /// let kind = RefKind::from(&shared).try_into_deref();
+    /// assert_eq!(kind.ok(), Some(RefKind::from(&42)));
+    ///
+    /// let mut unique = Rc::new(42);
+    /// let kind = RefKind::from(&mut unique).try_into_deref();
+    /// assert!(kind.is_err_and(|kind| kind.is_mut()));
+    /// ```
+    #[inline]
+    pub fn try_into_deref(self) -> Result<RefKind<'a, T::Target>, Self>
+    where
+        T: Deref,
+    {
+        match self {
+            Ref(shared) => Ok(Ref(shared)),
+            Mut(unique) => Err(Mut(unique)),
+        }
+    }
+
+    /// Converts `&RefKind<T>` into [`Ref`] of the target of the smart pointer,
+    /// in the same way as [`Option::as_deref`] does.
+    ///
+    /// Mutable kind of reference cannot be reborrowed through immutable reference,
+    /// so the result is always [`Ref`], whatever the kind of the `self` value is.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::RefKind;
+    ///
+    /// let mut name = String::from("player");
+    /// let kind = RefKind::from(&mut name);
+    /// assert_eq!(kind.as_deref(), RefKind::Ref("player"));
+    /// ```
+    #[inline]
+    pub fn as_deref(&self) -> RefKind<'_, T::Target>
+    where
+        T: Deref,
+    {
+        Ref(self.get_ref().deref())
+    }
+
+    /// Converts `&mut RefKind<T>` into [`RefKind`] of the target of the smart pointer,
+    /// preserving the kind of reference.
+    ///
+    /// Mutable kind of reference can only be reborrowed through mutable reference,
+    /// so unlike [`Option::as_deref`], this method borrows the `self` value mutably.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::RefKind;
+    ///
+    /// let mut boxed = Box::new(42);
+    /// let mut kind = RefKind::from(&mut boxed);
+    ///
+    /// if let RefKind::Mut(number) = kind.as_deref_mut() {
+    ///     *number += 1;
+    /// }
+    /// assert_eq!(**kind, 43);
+    /// ```
+    #[inline]
+    pub fn as_deref_mut(&mut self) -> RefKind<'_, T::Target>
+    where
+        T: DerefMut,
+    {
+        self.reborrow().into_deref()
+    }
+
+    /// Extends the collection behind the mutable reference with the contents of an iterator.
+    ///
+    /// Returns [`MoveError::BorrowedImmutably`] error without consuming the iterator
+    /// if the value is a [`Ref`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{MoveError, RefKind};
+    ///
+    /// let mut items = vec![1];
+    /// let mut kind = RefKind::from(&mut items);
+    /// assert_eq!(kind.try_extend([2, 3]), Ok(()));
+    /// assert_eq!(items, [1, 2, 3]);
+    ///
+    /// let mut kind = RefKind::from(&items);
+    /// assert_eq!(kind.try_extend([4]), Err(MoveError::BorrowedImmutably));
+    /// ```
+    pub fn try_extend<A, I>(&mut self, iter: I) -> MoveResult<()>
+    where
+        T: Extend<A>,
+        I: IntoIterator<Item = A>,
+    {
+        match self {
+            Ref(_) => Err(MoveError::BorrowedImmutably),
+            Mut(unique) => {
+                unique.extend(iter);
+                Ok(())
+            }
+        }
+    }
+
     /// Returns the contained [`Ref`] value, consuming the `self` value.
     ///
     /// # Panics
@@ -74,7 +302,7 @@ where
     /// Panics if the value is a [`Mut`].
     #[inline]
     #[track_caller]
-    pub fn unwrap_ref(self) -> &'a T {
+    pub const fn unwrap_ref(self) -> &'a T {
         match self {
             Ref(shared) => shared,
             Mut(_) => panic!("called `RefKind::unwrap_ref()` on a `RefKind::Mut` value"),
@@ -88,7 +316,7 @@ where
     /// Panics if the value is a [`Ref`].
     #[inline]
     #[track_caller]
-    pub fn unwrap_mut(self) -> &'a mut T {
+    pub const fn unwrap_mut(self) -> &'a mut T {
         match self {
             Ref(_) => panic!("called `RefKind::unwrap_mut()` on a `RefKind::Ref` value"),
             Mut(unique) => unique,
@@ -96,6 +324,69 @@ where
     }
 }
 
+impl<'a, T> RefKind<'a, T> {
+    /// Creates a table of immutable references on all of the array elements.
+    ///
+    /// This function can be used in `const` and `static` initializers,
+    /// so tables over `static` data are prepared at compile time
+    /// and only moving references out of them happens at runtime.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind};
+    ///
+    /// static SQUARES: [u32; 4] = [0, 1, 4, 9];
+    /// const TABLE: [Option<RefKind<'static, u32>>; 4] = RefKind::table(&SQUARES);
+    ///
+    /// // Each use of the constant is a fresh table with its own bookkeeping
+    /// let mut table = TABLE;
+    /// assert_eq!(table.try_move_ref(2), Ok(Some(&4)));
+    /// ```
+    pub const fn table<const N: usize>(items: &'a [T; N]) -> [Option<Self>; N] {
+        let mut table = [const { None }; N];
+        let mut index = 0;
+        while index < N {
+            table[index] = Some(Ref(&items[index]));
+            index += 1;
+        }
+        table
+    }
+}
+
+/// Coerces [`RefKind`] of the sized type into [`RefKind`] of the unsized type
+/// (e.g. the trait object), preserving the kind of reference.
+///
+/// Enums cannot be coerced automatically, so this macro rewraps the contained reference
+/// into the same kind of reference to the provided type.
+///
+/// ## Example
+///
+/// ```
+/// use core::fmt::Display;
+///
+/// use ref_kind::{unsize, RefKind};
+///
+/// let mut number = 42;
+/// let text = "hello";
+///
+/// let kinds: [RefKind<dyn Display>; 2] = [
+///     unsize!(RefKind::from(&mut number) => dyn Display),
+///     unsize!(RefKind::from(&text) => dyn Display),
+/// ];
+/// assert!(kinds[0].is_mut());
+/// assert_eq!(kinds[1].to_string(), "hello");
+/// ```
+#[macro_export]
+macro_rules! unsize {
+    ($kind:expr => $target:ty) => {
+        match $kind {
+            $crate::RefKind::Ref(shared) => $crate::RefKind::Ref(shared as &$target),
+            $crate::RefKind::Mut(unique) => $crate::RefKind::Mut(unique as &mut $target),
+        }
+    };
+}
+
 /// Convert immutable reference into [`RefKind`].
 impl<'a, T> From<&'a T> for RefKind<'a, T>
 where
@@ -134,6 +425,43 @@ where
     }
 }
 
+/// Indexing is forwarded to the contained reference of any kind.
+///
+/// This allows to use [`RefKind`] in the code generic over [`Index`] trait.
+impl<'a, T, I> Index<I> for RefKind<'a, T>
+where
+    T: ?Sized + Index<I> + 'a,
+{
+    type Output = T::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        self.get_ref().index(index)
+    }
+}
+
+/// Extending is forwarded to the contained mutable reference.
+///
+/// See [`RefKind::try_extend`] for the fallible version.
+///
+/// # Panics
+///
+/// Panics if the value is a [`Ref`].
+impl<'a, T, A> Extend<A> for RefKind<'a, T>
+where
+    T: ?Sized + Extend<A> + 'a,
+{
+    #[track_caller]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = A>,
+    {
+        if self.try_extend(iter).is_err() {
+            panic!("called `RefKind::extend()` on a `RefKind::Ref` value")
+        }
+    }
+}
+
 /// As suggested in this standard library [section].
 ///
 /// [section]: https://doc.rust-lang.org/std/convert/trait.AsRef.html#generic-implementations
@@ -147,3 +475,36 @@ where
         self.deref().as_ref()
     }
 }
+
+/// Future can be polled only through the mutable reference,
+/// so polling of [`Ref`] results in [`MoveError::BorrowedImmutably`] error.
+///
+/// ## Example
+///
+/// ```
+/// use core::{future::{self, Future}, pin::Pin, task::{Context, Poll, Waker}};
+///
+/// use ref_kind::{MoveError, RefKind};
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let mut future = future::ready(42);
+///
+/// let mut kind = RefKind::from(&mut future);
+/// assert_eq!(Pin::new(&mut kind).poll(&mut cx), Poll::Ready(Ok(42)));
+///
+/// let mut kind = RefKind::from(&future);
+/// assert_eq!(Pin::new(&mut kind).poll(&mut cx), Poll::Ready(Err(MoveError::BorrowedImmutably)));
+/// ```
+impl<'a, F> Future for RefKind<'a, F>
+where
+    F: ?Sized + Future + Unpin + 'a,
+{
+    type Output = MoveResult<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.get_mut() {
+            Ref(_) => Poll::Ready(Err(MoveError::BorrowedImmutably)),
+            Mut(unique) => Pin::new(&mut **unique).poll(cx).map(Ok),
+        }
+    }
+}
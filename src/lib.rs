@@ -1,6 +1,13 @@
 #![no_std]
 #![warn(missing_docs)]
-#![forbid(unsafe_code)]
+#![cfg_attr(
+    not(any(feature = "maybe-uninit", feature = "ffi", feature = "lock-free")),
+    forbid(unsafe_code)
+)]
+#![cfg_attr(
+    any(feature = "maybe-uninit", feature = "ffi", feature = "lock-free"),
+    deny(unsafe_code)
+)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 //! Different reference kinds in Rust.
@@ -14,7 +21,9 @@
 //! For that very case, crate defines some useful traits:
 //! - [`MoveRef`] and [`MoveMut`] for containers to retrieve corresponding kind of reference,
 //! - [`Move`] as a combination of the traits above,
-//! - [`Many`] for collections which is implemented for peekable iterators, [slices] and so on.
+//! - [`Many`] for collections which is implemented for peekable iterators, [slices] and so on,
+//! - [`ManyIndex`] for keys of slices, arrays and vectors, such as indices, ranges and arrays of indices,
+//! - [`ManyAvailability`] for collections which can report availability of the reference without moving it out.
 //!
 //! But nothing stops you to implement these traits for other types as well!
 //!
@@ -63,22 +72,71 @@
 //!
 //! ```toml
 //! [dependencies]
-//! ref_kind = { version = "0.5.0", default-features = false }
+//! ref_kind = { version = "0.6.0", default-features = false }
 //! ```
 //!
+//! ## Panic-free usage
+//!
+//! Panicking methods of this crate have fallible counterparts
+//! which returns an error instead, such as `try_move_ref` for `move_ref`.
+//! To make sure that only the fallible methods are used in your crate,
+//! forbid the panicking ones with [`disallowed_methods`][disallowed] lint of Clippy in `clippy.toml`:
+//!
+//! ```toml
+//! disallowed-methods = [
+//!     { path = "ref_kind::Many::move_ref", reason = "use `try_move_ref` instead" },
+//!     { path = "ref_kind::Many::move_mut", reason = "use `try_move_mut` instead" },
+//!     { path = "ref_kind::Many::move_ref_keyed", reason = "use `try_move_ref` instead" },
+//!     { path = "ref_kind::Many::move_mut_keyed", reason = "use `try_move_mut` instead" },
+//!     { path = "ref_kind::RefKind::unwrap_ref", reason = "match on the kind instead" },
+//!     { path = "ref_kind::RefKind::unwrap_mut", reason = "use `try_into_mut` instead" },
+//!     { path = "ref_kind::intern::SymbolTable::intern", reason = "use `try_intern` instead" },
+//! ]
+//! ```
+//!
+//! [disallowed]: https://rust-lang.github.io/rust-clippy/master/index.html#disallowed_methods
+//!
+//! ## Minimum supported Rust version
+//!
+//! This crate requires Rust 1.85 or newer. Feature `proptest` requires Rust 1.88 or newer,
+//! as required by the `proptest` crate itself.
+//!
 //! ## `#![forbid(unsafe_code)]`
 //!
-//! This crate contains no `unsafe` code.
+//! This crate contains no `unsafe` code, unless `maybe-uninit`, `ffi` or `lock-free` features are enabled.
+//!
+//! These features relax the lint to `#![deny(unsafe_code)]`, allowing `unsafe` code
+//! only in `RefKind::assume_init_kind`, in the `ffi` module and in the lock-free storage of `AtomicRefKind` respectively.
 //!
 //! ## Flags
 //!
 //! This crate has the following Cargo features:
 //!
-//! | Feature name | Description                                                                           |
-//! |--------------|---------------------------------------------------------------------------------------|
-//! | `alloc`      | Implements `Many` trait for `VecDeque` and `BTreeMap` in `alloc` crate                |
-//! | `std`        | Implements `Many` trait for `HashMap` in standard library, depends on `alloc` feature |
-//! | `hashbrown`  | Implements `Many` trait for `HashMap` in `hashbrown` crate                            |
+//! | Feature name       | Description                                                                                                                           |
+//! |--------------------|---------------------------------------------------------------------------------------------------------------------------------------|
+//! | `alloc`            | Implements `Many` trait for `VecDeque` and `BTreeMap` in `alloc` crate, provides `SparseSet`, `SortedMany`, `Tracked` and `Aliased`   |
+//! | `std`              | Implements `Many` trait for `HashMap` in standard library, provides `Rearmable`, depends on `alloc` feature                           |
+//! | `hashbrown`        | Implements `Many` trait for `HashMap` in `hashbrown` crate                                                                            |
+//! | `indexmap`         | Provides insertion-ordered `RefKindIndexMap` and implements `Many` trait for `IndexMap` in `indexmap` crate, depends on `std` feature |
+//! | `intern`           | Provides interned symbols which can be used as keys of string-keyed collections, depends on `std` feature                             |
+//! | `critical-section` | Provides `CriticalMany` wrapper which can be shared with interrupt handlers                                                           |
+//! | `cell`             | Implements `Many` trait for collections of optional references to `Cell`                                                              |
+//! | `atomic`           | Implements `Many` trait for collections of optional references to atomic values                                                       |
+//! | `bytemuck`         | Provides casts of `RefKind` of slices between `Pod` types of `bytemuck` crate                                                         |
+//! | `futures-core`     | Implements `Stream` trait for `RefKind` of streams                                                                                    |
+//! | `panic-payload`    | Panics with typed `MovePanic` payload instead of the message, depends on `std` feature                                                |
+//! | `proptest`         | Provides `proptest` strategies and operation model, depends on `std` feature                                                          |
+//! | `audit`            | Provides wrapper of collections which reports every move into the pluggable sink                                                      |
+//! | `metrics`          | Exports counters of moves, downgrades and conflicts through `metrics` crate, depends on `audit` feature                               |
+//! | `miette`           | Implements `Diagnostic` trait of `miette` crate for errors of moves, depends on `std` feature                                         |
+//! | `anyhow`           | Provides helpers attaching keys of moves to errors of `anyhow` crate, depends on `std` feature                                        |
+//! | `eyre`             | Provides helpers attaching keys of moves to reports of `eyre` crate, depends on `std` feature                                         |
+//! | `sync`             | Provides thread-safe containers of references, depends on `std` feature                                                               |
+//! | `lock-free`        | Stores references of `AtomicRefKind` without a mutex, which contains `unsafe` code, depends on `sync` feature                         |
+//! | `debug-ledger`     | Records moves of thread-safe containers for debugging, depends on `sync` and `audit` features                                         |
+//! | `debug-graph`      | Exports borrow state of collections as DOT or JSON, depends on `alloc` feature                                                        |
+//! | `maybe-uninit`     | Provides helpers to initialize `RefKind` of `MaybeUninit`, which contain `unsafe` code                                                |
+//! | `ffi`              | Provides C interface over the registry of pointers keyed by bytes, depends on `std` feature                                           |
 //!
 //! Feature `std` is enabled by default.
 //! You can disable it by using `default-features = false` in Cargo.toml.
@@ -97,21 +155,116 @@ extern crate alloc as alloc_crate;
 extern crate std as std_crate;
 
 pub use self::{
+    availability::{Availability, ManyAvailability},
+    by_address::ByAddress,
+    chain::{ChainKey, ManyChain},
+    index::{FromEnd, KeyIndex, ManyIndex},
+    key_mapped::KeyMapped,
+    keyed::Keyed,
     kind::RefKind,
     many::Many,
-    r#move::{Move, MoveError, MoveMut, MoveRef, Result},
+    r#move::{KeyedMoveError, Move, MoveError, MoveMut, MoveRef, MoveResult, MoveResultExt},
     RefKind::{Mut, Ref},
 };
 
+#[cfg(feature = "anyhow")]
+pub use self::anyhow::AnyhowMoveResultExt;
+#[cfg(feature = "critical-section")]
+pub use self::critical_section::CriticalMany;
+#[cfg(feature = "eyre")]
+pub use self::eyre::EyreMoveResultExt;
+#[cfg(feature = "indexmap")]
+pub use self::indexmap::RefKindIndexMap;
+#[cfg(feature = "panic-payload")]
+pub use self::r#move::MovePanic;
+#[allow(deprecated)]
+pub use self::r#move::Result;
+#[cfg(feature = "std")]
+pub use self::rearm::Rearmable;
+#[cfg(feature = "alloc")]
+pub use self::{
+    alias::Aliased,
+    compact::Compact,
+    cursor::ManyCursor,
+    ordered::{DequeMany, OrderedMany, PrefixMany},
+    sort::{DedupMany, SortMany},
+    sorted::SortedMany,
+    sparse_set::{RefKindSparseSet, SparseSet},
+    tracked::Tracked,
+};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod access;
+#[cfg(feature = "audit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+pub mod audit;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+#[cfg(feature = "debug-graph")]
+#[cfg_attr(docsrs, doc(cfg(feature = "debug-graph")))]
+pub mod graph;
+#[cfg(feature = "intern")]
+#[cfg_attr(docsrs, doc(cfg(feature = "intern")))]
+pub mod intern;
 pub mod iter;
+pub mod partition;
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+pub mod proptest;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod sparse_set;
+#[cfg(feature = "sync")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sync")))]
+pub mod sync;
 
+#[cfg(feature = "alloc")]
+mod alias;
 #[cfg(feature = "alloc")]
 mod alloc;
+#[cfg(feature = "anyhow")]
+mod anyhow;
+mod availability;
+mod by_address;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+mod chain;
+#[cfg(feature = "alloc")]
+mod compact;
+#[cfg(feature = "critical-section")]
+mod critical_section;
+#[cfg(feature = "alloc")]
+mod cursor;
+#[cfg(feature = "eyre")]
+mod eyre;
+#[cfg(feature = "futures-core")]
+mod futures_core;
 #[cfg(feature = "hashbrown")]
 mod hashbrown;
+mod index;
+#[cfg(feature = "indexmap")]
+mod indexmap;
+mod key_mapped;
+mod keyed;
 mod kind;
 mod many;
+#[cfg(feature = "maybe-uninit")]
+mod maybe_uninit;
+#[cfg(feature = "miette")]
+mod miette;
 mod r#move;
+#[cfg(feature = "alloc")]
+mod ordered;
+#[cfg(feature = "std")]
+mod rearm;
 mod slice;
+#[cfg(feature = "alloc")]
+mod sort;
+#[cfg(feature = "alloc")]
+mod sorted;
 #[cfg(feature = "std")]
 mod std;
+#[cfg(feature = "alloc")]
+mod tracked;
@@ -1,4 +1,6 @@
-use crate::{MoveError, Result};
+use core::fmt::Debug;
+
+use crate::MoveResult;
 
 /// Trait for collections which hold different kinds of reference.
 ///
@@ -17,7 +19,7 @@ pub trait Many<'a, Key> {
     ///
     /// This function copies an immutable reference or replaces mutable reference with immutable one,
     /// preserving an immutable reference in this collection.
-    fn try_move_ref(&mut self, key: Key) -> Result<Self::Ref>;
+    fn try_move_ref(&mut self, key: Key) -> MoveResult<Self::Ref>;
 
     /// Moves an immutable reference out of this collection.
     ///
@@ -35,11 +37,32 @@ pub trait Many<'a, Key> {
         }
     }
 
+    /// Moves an immutable reference out of this collection,
+    /// reporting the key in the panic if the reference cannot be moved out.
+    ///
+    /// This function behaves like [`move_ref`](Many::move_ref),
+    /// but the key is cloned before the move so that the panic message
+    /// (or `MovePanic` payload) could contain its [`Debug`] representation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the collection.
+    #[track_caller]
+    fn move_ref_keyed(&mut self, key: Key) -> Self::Ref
+    where
+        Key: Debug + Clone,
+    {
+        match self.try_move_ref(key.clone()) {
+            Ok(result) => result,
+            Err(error) => move_keyed_panic(error, &key),
+        }
+    }
+
     /// The type of a mutable reference which is being moved out.
     type Mut: 'a;
 
     /// Tries to move a mutable reference out of this collection.
-    fn try_move_mut(&mut self, key: Key) -> Result<Self::Mut>;
+    fn try_move_mut(&mut self, key: Key) -> MoveResult<Self::Mut>;
 
     /// Moves a mutable reference out of this collection.
     ///
@@ -54,10 +77,50 @@ pub trait Many<'a, Key> {
             Err(error) => move_panic(error),
         }
     }
+
+    /// Moves a mutable reference out of this collection,
+    /// reporting the key in the panic if the reference cannot be moved out.
+    ///
+    /// This function behaves like [`move_mut`](Many::move_mut),
+    /// but the key is cloned before the move so that the panic message
+    /// (or `MovePanic` payload) could contain its [`Debug`] representation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the collection
+    /// or the value was already borrowed as immutable.
+    #[track_caller]
+    fn move_mut_keyed(&mut self, key: Key) -> Self::Mut
+    where
+        Key: Debug + Clone,
+    {
+        match self.try_move_mut(key.clone()) {
+            Ok(option) => option,
+            Err(error) => move_keyed_panic(error, &key),
+        }
+    }
 }
 
 #[cold]
 #[track_caller]
-fn move_panic(error: MoveError) -> ! {
+pub(crate) fn move_panic(error: crate::MoveError) -> ! {
+    #[cfg(feature = "panic-payload")]
+    std_crate::panic::panic_any(crate::MovePanic {
+        error,
+        key_debug: None,
+    });
+    #[cfg(not(feature = "panic-payload"))]
     panic!("{}", error)
 }
+
+#[cold]
+#[track_caller]
+pub(crate) fn move_keyed_panic(error: crate::MoveError, key: &dyn Debug) -> ! {
+    #[cfg(feature = "panic-payload")]
+    std_crate::panic::panic_any(crate::MovePanic {
+        error,
+        key_debug: Some(std_crate::format!("{key:?}")),
+    });
+    #[cfg(not(feature = "panic-payload"))]
+    panic!("failed to move reference by key {key:?}: {error}")
+}
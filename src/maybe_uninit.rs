@@ -0,0 +1,70 @@
+use core::mem::MaybeUninit;
+
+use crate::{Mut, Ref, RefKind};
+
+impl<'a, T> RefKind<'a, MaybeUninit<T>> {
+    /// Initializes the referent of the mutable reference with the value,
+    /// returning the mutable reference to the initialized value.
+    ///
+    /// Immutable reference cannot be written to,
+    /// so it is returned back together with the value as an error.
+    ///
+    /// Note that the old value is overwritten without being dropped,
+    /// in the same way as by [`MaybeUninit::write`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    ///
+    /// use ref_kind::RefKind;
+    ///
+    /// let mut storage = MaybeUninit::uninit();
+    /// let kind = RefKind::from(&mut storage);
+    ///
+    /// let number = kind.write(42).unwrap();
+    /// assert_eq!(number, RefKind::from(&mut 42));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "maybe-uninit")))]
+    pub fn write(self, value: T) -> Result<RefKind<'a, T>, (Self, T)> {
+        match self {
+            Ref(shared) => Err((Ref(shared), value)),
+            Mut(unique) => Ok(Mut(unique.write(value))),
+        }
+    }
+
+    /// Converts the reference to the possibly uninitialized value
+    /// into the reference to the initialized one, preserving the kind of reference.
+    ///
+    /// # Safety
+    ///
+    /// The referent must be initialized, as required by
+    /// [`MaybeUninit::assume_init_ref`] and [`MaybeUninit::assume_init_mut`].
+    /// For the mutable reference, the returned reference also allows to move the value out
+    /// or to drop it in place, after which the referent must not be assumed to be initialized again.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    ///
+    /// use ref_kind::RefKind;
+    ///
+    /// let storage = MaybeUninit::new(42);
+    /// let kind = RefKind::from(&storage);
+    ///
+    /// // SAFETY: storage was initialized by `MaybeUninit::new`
+    /// let number = unsafe { kind.assume_init_kind() };
+    /// assert_eq!(number, RefKind::from(&42));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "maybe-uninit")))]
+    #[allow(unsafe_code)]
+    pub unsafe fn assume_init_kind(self) -> RefKind<'a, T> {
+        match self {
+            // SAFETY: the caller guarantees that the referent is initialized
+            Ref(shared) => Ref(unsafe { shared.assume_init_ref() }),
+            // SAFETY: the caller guarantees that the referent is initialized
+            Mut(unique) => Mut(unsafe { unique.assume_init_mut() }),
+        }
+    }
+}
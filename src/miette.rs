@@ -0,0 +1,90 @@
+use core::fmt;
+
+use miette::Diagnostic;
+use std_crate::boxed::Box;
+
+use crate::{access::ClaimError, KeyedMoveError, MoveError};
+
+impl MoveError {
+    const fn code(&self) -> &'static str {
+        match self {
+            Self::BorrowedImmutably => "ref_kind::borrowed_immutably",
+            Self::BorrowedMutably => "ref_kind::borrowed_mutably",
+            Self::Poisoned => "ref_kind::poisoned",
+            Self::NotFound => "ref_kind::not_found",
+        }
+    }
+
+    const fn help(&self) -> &'static str {
+        match self {
+            Self::BorrowedImmutably => {
+                "this key's reference was already moved as immutable; \
+                consider move_ref or restructure your key sets"
+            }
+            Self::BorrowedMutably => {
+                "this key's mutable reference was already moved; \
+                consider move_ref or restructure your key sets"
+            }
+            Self::Poisoned => {
+                "some thread panicked while moving references out; \
+                clear the poisoning once the collection is known to be consistent"
+            }
+            Self::NotFound => {
+                "the collection holds no reference by this key; \
+                insert it before claiming or remove it from your key sets"
+            }
+        }
+    }
+}
+
+/// Provides error code and help text for the error of the move.
+///
+/// ## Example
+///
+/// ```
+/// use miette::Diagnostic;
+/// use ref_kind::MoveError;
+///
+/// let error = MoveError::BorrowedMutably;
+/// assert_eq!(error.code().unwrap().to_string(), "ref_kind::borrowed_mutably");
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "miette")))]
+impl Diagnostic for MoveError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(MoveError::code(self)))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(MoveError::help(self)))
+    }
+}
+
+/// Provides error code and help text of the underlying error of the move.
+#[cfg_attr(docsrs, doc(cfg(feature = "miette")))]
+impl<Key> Diagnostic for ClaimError<Key>
+where
+    Key: fmt::Debug,
+{
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.error.code()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.error.help()))
+    }
+}
+
+/// Provides error code and help text of the underlying error of the move.
+#[cfg_attr(docsrs, doc(cfg(feature = "miette")))]
+impl<Key> Diagnostic for KeyedMoveError<Key>
+where
+    Key: fmt::Debug,
+{
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.error.code()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.error.help()))
+    }
+}
@@ -1,9 +1,17 @@
 /// The result of moving reference out of the value.
-pub type Result<T> = core::result::Result<T, MoveError>;
+pub type MoveResult<T> = core::result::Result<T, MoveError>;
+
+/// The result of moving reference out of the value.
+#[deprecated(since = "0.6.0", note = "use `MoveResult` instead")]
+pub type Result<T> = MoveResult<T>;
 
 /// Enum that defines errors which can occur when moving reference
 /// out of the value.
+///
+/// New kinds of errors may be added in the future,
+/// so matching on this enum requires a wildcard arm.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum MoveError {
     /// Reference was already moved out of the collection as immutable.
     /// It is not allowed to get mutable reference again, but it is allowed to get immutable one.
@@ -11,6 +19,11 @@ pub enum MoveError {
     /// Reference was already moved out of the collection as mutable.
     /// It is not allowed to get neither immutable nor mutable reference again.
     BorrowedMutably,
+    /// Collection was poisoned because some thread panicked while moving reference out of it.
+    /// It is not allowed to move references out of the collection until poisoning is cleared.
+    Poisoned,
+    /// Collection contains no reference by the key which was required to be present.
+    NotFound,
 }
 
 impl core::fmt::Display for MoveError {
@@ -18,6 +31,8 @@ impl core::fmt::Display for MoveError {
         match self {
             Self::BorrowedImmutably => write!(f, "reference was already borrowed immutably"),
             Self::BorrowedMutably => write!(f, "reference was already borrowed mutably"),
+            Self::Poisoned => write!(f, "collection was poisoned"),
+            Self::NotFound => write!(f, "reference was not found by the key"),
         }
     }
 }
@@ -25,3 +40,57 @@ impl core::fmt::Display for MoveError {
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl std_crate::error::Error for MoveError {}
+
+/// Payload of the panic which occurs when reference cannot be moved out of the collection.
+///
+/// Panicking methods such as `Many::move_mut` panic with this payload,
+/// so frameworks which catch panics (e.g. test harnesses or plugin hosts)
+/// can recover the error by [downcasting](core::any::Any) the payload.
+/// The key is recovered only from the panics of keyed methods such as `Many::move_mut_keyed`.
+///
+/// Note that the default panic hook prints only string payloads,
+/// so the message of the error is not printed to the standard error output.
+///
+/// ## Example
+///
+/// ```
+/// use std::panic::{self, AssertUnwindSafe};
+///
+/// use ref_kind::{Many, MoveError, MovePanic, RefKind};
+///
+/// let mut number = 42;
+/// let mut many = [Some(RefKind::from(&mut number))];
+/// let _ = many.move_mut(0);
+///
+/// let payload = panic::catch_unwind(AssertUnwindSafe(|| many.move_ref_keyed(0))).unwrap_err();
+/// let payload = payload.downcast_ref::<MovePanic>().unwrap();
+/// assert_eq!(payload.error, MoveError::BorrowedMutably);
+/// assert_eq!(payload.key_debug.as_deref(), Some("0"));
+/// ```
+#[cfg(feature = "panic-payload")]
+#[cfg_attr(docsrs, doc(cfg(feature = "panic-payload")))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MovePanic {
+    /// The reason why the reference could not be moved out.
+    pub error: MoveError,
+    /// The key by which the reference was moved out, formatted with its [`Debug`](core::fmt::Debug) implementation.
+    ///
+    /// This is [`None`] unless the panic occurred in the keyed method, such as `Many::move_mut_keyed`.
+    pub key_debug: Option<std_crate::string::String>,
+}
+
+#[cfg(feature = "panic-payload")]
+impl core::fmt::Display for MovePanic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self {
+                error,
+                key_debug: Some(key_debug),
+            } => write!(f, "failed to move reference by key {key_debug}: {error}"),
+            Self {
+                error,
+                key_debug: None,
+            } => write!(f, "{error}"),
+        }
+    }
+}
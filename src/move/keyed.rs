@@ -0,0 +1,132 @@
+use core::fmt;
+
+use super::{MoveError, MoveResult};
+
+/// Error of the move with the key by which the reference could not be moved out,
+/// and optionally the label of the collection.
+///
+/// This struct is created by [`MoveResultExt`] methods.
+/// It implements standard `Error` trait, so it can be bubbled up
+/// through error reporting crates such as `anyhow` or `eyre`
+/// with the key and the collection being a part of the message.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyedMoveError<Key> {
+    /// Key by which the reference could not be moved out.
+    pub key: Key,
+    /// The reason why the reference could not be moved out.
+    pub error: MoveError,
+    /// Label of the collection which the reference could not be moved out of, if any.
+    pub container: Option<&'static str>,
+}
+
+impl<Key> KeyedMoveError<Key> {
+    /// Creates new error of the move by the key.
+    pub const fn new(key: Key, error: MoveError) -> Self {
+        Self {
+            key,
+            error,
+            container: None,
+        }
+    }
+
+    /// Attaches the label of the collection to the error, consuming the `self` value.
+    pub fn with_container(self, container: &'static str) -> Self {
+        let container = Some(container);
+        Self { container, ..self }
+    }
+}
+
+impl<Key> fmt::Display for KeyedMoveError<Key>
+where
+    Key: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_context(f)?;
+        write!(f, ": {}", self.error)
+    }
+}
+
+impl<Key> KeyedMoveError<Key>
+where
+    Key: fmt::Debug,
+{
+    fn fmt_context(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { key, container, .. } = self;
+        match container {
+            Some(container) => write!(
+                f,
+                "failed to move reference out of {container} by key {key:?}"
+            ),
+            None => write!(f, "failed to move reference by key {key:?}"),
+        }
+    }
+
+    /// Returns the message of this error without the underlying error of the move.
+    #[cfg(any(feature = "anyhow", feature = "eyre"))]
+    pub(crate) fn context(&self) -> std_crate::string::String {
+        struct Context<'e, Key>(&'e KeyedMoveError<Key>);
+
+        impl<Key> fmt::Display for Context<'_, Key>
+        where
+            Key: fmt::Debug,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt_context(f)
+            }
+        }
+
+        std_crate::string::ToString::to_string(&Context(self))
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<Key> std_crate::error::Error for KeyedMoveError<Key>
+where
+    Key: fmt::Debug,
+{
+    fn source(&self) -> Option<&(dyn std_crate::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Extension trait which attaches the key to the error of the move.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Many, MoveResultExt, RefKind};
+///
+/// let mut numbers = [0, 1, 2];
+/// let mut many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+///
+/// let _ = many.try_move_mut(1).with_key(1).unwrap();
+/// let error = many.try_move_ref(1).with_key(1).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "failed to move reference by key 1: reference was already borrowed mutably",
+/// );
+///
+/// let error = many.try_move_ref(1).with_key_in("numbers", 1).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "failed to move reference out of numbers by key 1: reference was already borrowed mutably",
+/// );
+/// ```
+pub trait MoveResultExt<T> {
+    /// Attaches the key to the error of the move, if any.
+    fn with_key<Key>(self, key: Key) -> Result<T, KeyedMoveError<Key>>;
+
+    /// Attaches the key and the label of the collection to the error of the move, if any.
+    fn with_key_in<Key>(self, container: &'static str, key: Key) -> Result<T, KeyedMoveError<Key>>;
+}
+
+impl<T> MoveResultExt<T> for MoveResult<T> {
+    fn with_key<Key>(self, key: Key) -> Result<T, KeyedMoveError<Key>> {
+        self.map_err(|error| KeyedMoveError::new(key, error))
+    }
+
+    fn with_key_in<Key>(self, container: &'static str, key: Key) -> Result<T, KeyedMoveError<Key>> {
+        self.map_err(|error| KeyedMoveError::new(key, error).with_container(container))
+    }
+}
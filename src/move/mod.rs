@@ -1,11 +1,18 @@
 pub use self::{
-    error::{MoveError, Result},
+    error::{MoveError, MoveResult},
+    keyed::{KeyedMoveError, MoveResultExt},
     move_mut::MoveMut,
     move_ref::MoveRef,
     r#move::Move,
 };
 
+#[cfg(feature = "panic-payload")]
+pub use self::error::MovePanic;
+#[allow(deprecated)]
+pub use self::error::Result;
+
 mod error;
+mod keyed;
 mod r#move;
 mod move_mut;
 mod move_ref;
@@ -1,6 +1,6 @@
 #![allow(clippy::module_inception)]
 
-use crate::{Many, Result};
+use crate::{Many, MoveResult};
 
 use super::{MoveMut, MoveRef};
 
@@ -20,13 +20,13 @@ where
 {
     type Ref = <Self as MoveRef<'owner>>::Ref;
 
-    fn try_move_ref(&mut self, _: K) -> Result<Self::Ref> {
+    fn try_move_ref(&mut self, _: K) -> MoveResult<Self::Ref> {
         MoveRef::move_ref(self)
     }
 
     type Mut = <Self as MoveMut<'owner>>::Mut;
 
-    fn try_move_mut(&mut self, _: K) -> Result<Self::Mut> {
+    fn try_move_mut(&mut self, _: K) -> MoveResult<Self::Mut> {
         MoveMut::move_mut(self)
     }
 }
@@ -1,6 +1,11 @@
+#[cfg(feature = "cell")]
+use core::cell::Cell;
+#[cfg(feature = "atomic")]
+use core::sync::atomic;
+
 use crate::{Mut, Ref, RefKind};
 
-use super::{MoveError, Result};
+use super::{MoveError, MoveResult};
 
 /// Trait for containers which hold *mutable* kind of reference.
 ///
@@ -16,7 +21,7 @@ pub trait MoveMut<'owner> {
     type Mut: 'owner;
 
     /// Tries to move a mutable reference out of the container.
-    fn move_mut(&mut self) -> Result<Self::Mut>;
+    fn move_mut(&mut self) -> MoveResult<Self::Mut>;
 }
 
 /// Mutable reference should be moved out of the [`Option`].
@@ -26,7 +31,7 @@ where
 {
     type Mut = &'owner mut T;
 
-    fn move_mut(&mut self) -> Result<Self::Mut> {
+    fn move_mut(&mut self) -> MoveResult<Self::Mut> {
         let unique = self.take().ok_or(MoveError::BorrowedMutably)?;
         Ok(unique)
     }
@@ -40,7 +45,7 @@ where
 {
     type Mut = &'owner mut T;
 
-    fn move_mut(&mut self) -> Result<Self::Mut> {
+    fn move_mut(&mut self) -> MoveResult<Self::Mut> {
         let kind = self.take().ok_or(MoveError::BorrowedMutably)?;
 
         let unique = match kind {
@@ -53,3 +58,68 @@ where
         Ok(unique)
     }
 }
+
+/// Reference to the [`Cell`] allows shared mutation,
+/// so it can be trivially copied both as immutable and as mutable reference.
+///
+/// This allows collections of optional references to cells (e.g. fields of grid simulations)
+/// to be used as [`Many`](crate::Many) collections alongside the collections of real references.
+///
+/// ## Example
+///
+/// ```
+/// use core::cell::Cell;
+///
+/// use ref_kind::Many;
+///
+/// let grid = [Cell::new(0), Cell::new(1), Cell::new(2)];
+/// let mut many = grid.each_ref().map(Some);
+///
+/// let (left, right) = (many.try_move_mut(0).unwrap(), many.try_move_mut(2).unwrap());
+/// let left_again = many.try_move_mut(0).unwrap();
+/// left.unwrap().set(right.unwrap().get());
+/// assert_eq!(left_again.unwrap().get(), 2);
+/// ```
+#[cfg(feature = "cell")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cell")))]
+impl<'owner, T> MoveMut<'owner> for Option<&'owner Cell<T>>
+where
+    T: ?Sized,
+{
+    type Mut = &'owner Cell<T>;
+
+    fn move_mut(&mut self) -> MoveResult<Self::Mut> {
+        let cell = self.ok_or(MoveError::BorrowedMutably)?;
+        Ok(cell)
+    }
+}
+
+#[cfg(feature = "atomic")]
+macro_rules! impl_move_mut_atomic {
+    ($($size:literal => $($atomic:ident),+;)+) => {$($(
+        /// Reference to the atomic value allows shared mutation through atomic operations,
+        /// so it can be trivially copied both as immutable and as mutable reference.
+        ///
+        /// This allows counters and flags to be used as [`Many`](crate::Many) collections
+        /// alongside the collections of real references without claiming exclusivity.
+        #[cfg(target_has_atomic = $size)]
+        #[cfg_attr(docsrs, doc(cfg(all(feature = "atomic", target_has_atomic = $size))))]
+        impl<'owner> MoveMut<'owner> for Option<&'owner atomic::$atomic> {
+            type Mut = &'owner atomic::$atomic;
+
+            fn move_mut(&mut self) -> MoveResult<Self::Mut> {
+                let atomic = self.ok_or(MoveError::BorrowedMutably)?;
+                Ok(atomic)
+            }
+        }
+    )+)+};
+}
+
+#[cfg(feature = "atomic")]
+impl_move_mut_atomic! {
+    "8" => AtomicBool, AtomicI8, AtomicU8;
+    "16" => AtomicI16, AtomicU16;
+    "32" => AtomicI32, AtomicU32;
+    "64" => AtomicI64, AtomicU64;
+    "ptr" => AtomicIsize, AtomicUsize;
+}
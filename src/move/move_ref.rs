@@ -1,6 +1,6 @@
 use crate::{Ref, RefKind};
 
-use super::{MoveError, Result};
+use super::{MoveError, MoveResult};
 
 /// Trait for containers which hold *immutable* kind of reference.
 ///
@@ -19,7 +19,7 @@ pub trait MoveRef<'owner> {
     ///
     /// This function can copy an immutable reference or replace mutable reference with immutable one,
     /// preserving an immutable reference in the container.
-    fn move_ref(&mut self) -> Result<Self::Ref>;
+    fn move_ref(&mut self) -> MoveResult<Self::Ref>;
 }
 
 /// Immutable reference can be trivially copied.
@@ -29,7 +29,7 @@ where
 {
     type Ref = &'owner T;
 
-    fn move_ref(&mut self) -> Result<Self::Ref> {
+    fn move_ref(&mut self) -> MoveResult<Self::Ref> {
         Ok(self)
     }
 }
@@ -41,7 +41,7 @@ where
 {
     type Ref = &'owner T;
 
-    fn move_ref(&mut self) -> Result<Self::Ref> {
+    fn move_ref(&mut self) -> MoveResult<Self::Ref> {
         let shared = self.ok_or(MoveError::BorrowedImmutably)?;
         Ok(shared)
     }
@@ -55,7 +55,7 @@ where
 {
     type Ref = &'owner T;
 
-    fn move_ref(&mut self) -> Result<Self::Ref> {
+    fn move_ref(&mut self) -> MoveResult<Self::Ref> {
         let unique = self.take().ok_or(MoveError::BorrowedMutably)?;
         Ok(unique)
     }
@@ -70,7 +70,7 @@ where
 {
     type Ref = &'owner T;
 
-    fn move_ref(&mut self) -> Result<Self::Ref> {
+    fn move_ref(&mut self) -> MoveResult<Self::Ref> {
         let kind = self.take().ok_or(MoveError::BorrowedMutably)?;
 
         let shared = kind.into_ref();
@@ -0,0 +1,288 @@
+use alloc_crate::{
+    collections::{BTreeMap, VecDeque},
+    vec::Vec,
+};
+use core::{
+    borrow::Borrow,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{MoveMut, MoveResult, Mut, RefKind};
+
+/// Trait for ordered collections which allow to move references out
+/// by the smallest or the largest key which reference is still available.
+///
+/// This is useful for priority-queue-like consumers, which cannot know
+/// which keys are still available without trying each of them.
+/// Deques are keyed by index, so their front and back are the first and the last keys,
+/// which allows producer-consumer pipelines to claim references in FIFO order.
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use ref_kind::{OrderedMany, RefKind};
+///
+/// let (mut low, mut middle, high) = (1, 5, 10);
+/// let mut many = BTreeMap::from([
+///     (1, Some(RefKind::from(&mut low))),
+///     (5, Some(RefKind::from(&mut middle))),
+///     (10, Some(RefKind::from(&high))),
+/// ]);
+///
+/// assert_eq!(many.move_first_mut(), Some((1, &mut 1)));
+/// assert_eq!(many.move_first_mut(), Some((5, &mut 5)));
+/// // Immutable reference cannot be moved out as mutable one
+/// assert_eq!(many.move_last_mut(), None);
+///
+/// assert_eq!(many.pop_move_first(), Some((10, RefKind::from(&10))));
+/// assert_eq!(many.len(), 2);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait OrderedMany<'a, K, T>
+where
+    T: ?Sized + 'a,
+{
+    /// Moves a mutable reference out of the collection by the smallest key
+    /// which mutable reference is still available, returning the key too.
+    ///
+    /// Returns [`None`] if there are no mutable references left in the collection.
+    fn move_first_mut(&mut self) -> Option<(K, &'a mut T)>;
+
+    /// Moves a mutable reference out of the collection by the largest key
+    /// which mutable reference is still available, returning the key too.
+    ///
+    /// Returns [`None`] if there are no mutable references left in the collection.
+    fn move_last_mut(&mut self) -> Option<(K, &'a mut T)>;
+
+    /// Removes an entry with the smallest key which reference is still available
+    /// out of the collection, returning the key and the kind of reference.
+    ///
+    /// Entries which references were already moved out are skipped and stay in the collection.
+    /// Returns [`None`] if there are no references left in the collection.
+    ///
+    /// Deques are keyed by index, so removing an entry shifts keys of all the entries after it.
+    /// That is why deques only pop their front entry, returning [`None`]
+    /// if its reference was already moved out.
+    fn pop_move_first(&mut self) -> Option<(K, RefKind<'a, T>)>;
+
+    /// Moves every mutable reference which is still available out of the collection
+    /// by the keys in the range, returning them with their keys in the ascending order of keys.
+    ///
+    /// Entries which mutable references are not available are skipped.
+    fn move_range_mut<R>(&mut self, range: R) -> Vec<(K, &'a mut T)>
+    where
+        R: RangeBounds<K>;
+}
+
+impl<'a, K, T> OrderedMany<'a, K, T> for BTreeMap<K, Option<RefKind<'a, T>>>
+where
+    K: Ord + Clone,
+    T: ?Sized + 'a,
+{
+    fn move_first_mut(&mut self) -> Option<(K, &'a mut T)> {
+        let (key, item) = self
+            .iter_mut()
+            .find(|(_, item)| matches!(item, Some(Mut(_))))?;
+        let unique = item.take()?.into_mut()?;
+        Some((key.clone(), unique))
+    }
+
+    fn move_last_mut(&mut self) -> Option<(K, &'a mut T)> {
+        let (key, item) = self
+            .iter_mut()
+            .rev()
+            .find(|(_, item)| matches!(item, Some(Mut(_))))?;
+        let unique = item.take()?.into_mut()?;
+        Some((key.clone(), unique))
+    }
+
+    fn pop_move_first(&mut self) -> Option<(K, RefKind<'a, T>)> {
+        let key = self.iter().find(|(_, item)| item.is_some())?.0.clone();
+        let kind = self.remove(&key)??;
+        Some((key, kind))
+    }
+
+    fn move_range_mut<R>(&mut self, range: R) -> Vec<(K, &'a mut T)>
+    where
+        R: RangeBounds<K>,
+    {
+        let items = self.range_mut(range);
+        move_available_mut(items.map(|(key, item)| (key.clone(), item)))
+    }
+}
+
+impl<'a, T> OrderedMany<'a, usize, T> for VecDeque<Option<RefKind<'a, T>>>
+where
+    T: ?Sized + 'a,
+{
+    fn move_first_mut(&mut self) -> Option<(usize, &'a mut T)> {
+        let (index, item) = self
+            .iter_mut()
+            .enumerate()
+            .find(|(_, item)| matches!(item, Some(Mut(_))))?;
+        let unique = item.take()?.into_mut()?;
+        Some((index, unique))
+    }
+
+    fn move_last_mut(&mut self) -> Option<(usize, &'a mut T)> {
+        let (index, item) = self
+            .iter_mut()
+            .enumerate()
+            .rev()
+            .find(|(_, item)| matches!(item, Some(Mut(_))))?;
+        let unique = item.take()?.into_mut()?;
+        Some((index, unique))
+    }
+
+    fn pop_move_first(&mut self) -> Option<(usize, RefKind<'a, T>)> {
+        let kind = self.pop_front_kind()?;
+        Some((0, kind))
+    }
+
+    fn move_range_mut<R>(&mut self, range: R) -> Vec<(usize, &'a mut T)>
+    where
+        R: RangeBounds<usize>,
+    {
+        let items = self.iter_mut().enumerate();
+        move_available_mut(items.filter(|(index, _)| range.contains(index)))
+    }
+}
+
+/// Trait for deques which allow to move references out of their front and back in FIFO order.
+///
+/// Unlike [`OrderedMany::move_first_mut`], which skips unavailable entries,
+/// these methods operate strictly on the front or back entry,
+/// so consumers are notified with an error if the entry is busy.
+/// Deques are rotated with inherent [`VecDeque::rotate_left`] and [`VecDeque::rotate_right`] methods,
+/// which move entries together with their kinds of reference.
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::VecDeque;
+///
+/// use ref_kind::{DequeMany, Many, MoveError, RefKind};
+///
+/// let (mut first, mut second, mut third) = (1, 2, 3);
+/// let mut queue = VecDeque::from([
+///     Some(RefKind::from(&mut first)),
+///     Some(RefKind::from(&mut second)),
+///     Some(RefKind::from(&mut third)),
+/// ]);
+///
+/// assert_eq!(queue.pop_front_kind(), Some(RefKind::from(&mut 1)));
+/// let _ = queue.try_move_ref(0);
+/// // Front entry is borrowed immutably, so it is reported instead of being skipped
+/// assert_eq!(queue.move_front_mut(), Err(MoveError::BorrowedImmutably));
+/// assert_eq!(queue.move_back_mut(), Ok(Some(&mut 3)));
+///
+/// queue.rotate_left(1);
+/// assert_eq!(queue.move_front_mut(), Err(MoveError::BorrowedMutably));
+/// assert_eq!(queue.pop_front_kind(), None);
+/// assert_eq!(queue.len(), 2);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait DequeMany<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    /// Moves a mutable reference out of the front entry of the deque.
+    ///
+    /// Returns [`None`] if the deque is empty,
+    /// or an error if the reference of the front entry is not available as mutable.
+    fn move_front_mut(&mut self) -> MoveResult<Option<&'a mut T>>;
+
+    /// Moves a mutable reference out of the back entry of the deque.
+    ///
+    /// Returns [`None`] if the deque is empty,
+    /// or an error if the reference of the back entry is not available as mutable.
+    fn move_back_mut(&mut self) -> MoveResult<Option<&'a mut T>>;
+
+    /// Removes the front entry of the deque, returning its kind of reference.
+    ///
+    /// Returns [`None`] if the deque is empty or the reference of the front entry was already moved out,
+    /// in which case the entry stays in the deque.
+    fn pop_front_kind(&mut self) -> Option<RefKind<'a, T>>;
+}
+
+impl<'a, T> DequeMany<'a, T> for VecDeque<Option<RefKind<'a, T>>>
+where
+    T: ?Sized + 'a,
+{
+    fn move_front_mut(&mut self) -> MoveResult<Option<&'a mut T>> {
+        self.front_mut().map(MoveMut::move_mut).transpose()
+    }
+
+    fn move_back_mut(&mut self) -> MoveResult<Option<&'a mut T>> {
+        self.back_mut().map(MoveMut::move_mut).transpose()
+    }
+
+    fn pop_front_kind(&mut self) -> Option<RefKind<'a, T>> {
+        self.front()?.as_ref()?;
+        self.pop_front()?
+    }
+}
+
+/// Trait for ordered collections with string-like keys
+/// which allow to move references out by the prefix of the key.
+///
+/// This is useful for namespaced keys such as `audio.volume` or `audio.muted`.
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use ref_kind::{PrefixMany, RefKind};
+///
+/// let (mut volume, mut balance, mut width) = (50, 0, 800);
+/// let mut many = BTreeMap::from([
+///     ("audio.balance", Some(RefKind::from(&mut balance))),
+///     ("audio.volume", Some(RefKind::from(&mut volume))),
+///     ("video.width", Some(RefKind::from(&mut width))),
+/// ]);
+///
+/// let audio = many.move_prefix_mut("audio.");
+/// assert_eq!(audio, [("audio.balance", &mut 0), ("audio.volume", &mut 50)]);
+/// // References were already moved out
+/// assert!(many.move_prefix_mut("audio.").is_empty());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait PrefixMany<'a, K, T>
+where
+    T: ?Sized + 'a,
+{
+    /// Moves every mutable reference which is still available out of the collection
+    /// by the keys which start with the prefix, returning them with their keys in the ascending order of keys.
+    ///
+    /// Entries which mutable references are not available are skipped.
+    fn move_prefix_mut(&mut self, prefix: &str) -> Vec<(K, &'a mut T)>;
+}
+
+impl<'a, K, T> PrefixMany<'a, K, T> for BTreeMap<K, Option<RefKind<'a, T>>>
+where
+    K: Ord + Borrow<str> + Clone,
+    T: ?Sized + 'a,
+{
+    fn move_prefix_mut(&mut self, prefix: &str) -> Vec<(K, &'a mut T)> {
+        let range = (Bound::Included(prefix), Bound::Unbounded);
+        let items = self
+            .range_mut::<str, _>(range)
+            .take_while(|(key, _)| (*key).borrow().starts_with(prefix));
+        move_available_mut(items.map(|(key, item)| (key.clone(), item)))
+    }
+}
+
+fn move_available_mut<'s, 'a, K, T, I>(items: I) -> Vec<(K, &'a mut T)>
+where
+    T: ?Sized + 'a,
+    'a: 's,
+    I: Iterator<Item = (K, &'s mut Option<RefKind<'a, T>>)>,
+{
+    items
+        .filter(|(_, item)| matches!(item, Some(Mut(_))))
+        .filter_map(|(key, item)| Some((key, item.take()?.into_mut()?)))
+        .collect()
+}
@@ -0,0 +1,489 @@
+//! Provides partitioning of [slices](prim@slice) into disjoint views
+//! and implementation of [`Many`] trait for these views,
+//! as well as chunked, windowed and pairwise access to slices of optional [`RefKind`]s.
+
+use core::{array, fmt, iter::FusedIterator, mem, ops::Range, slice};
+
+use crate::{Availability, Many, MoveError, MoveResult, Mut, Ref, RefKind};
+
+/// Trait for collections which can be partitioned into disjoint views.
+pub trait PartitionMany<T> {
+    /// Partitions the collection into `n` views over disjoint ranges of indices.
+    ///
+    /// Each view supports keyed moves by the indices of the original collection,
+    /// so views can be processed independently, for example, by different threads.
+    /// Lengths of the views differ by one at most.
+    /// If `n` is zero, no views are returned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{partition::PartitionMany, Many, RefKind};
+    ///
+    /// let mut numbers = [0, 1, 2, 3, 4];
+    /// let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    ///
+    /// let mut partitions = many.partition_many(2);
+    /// let mut first = partitions.next().unwrap();
+    /// let mut second = partitions.next().unwrap();
+    /// assert!(partitions.next().is_none());
+    ///
+    /// assert_eq!(first.keys(), 0..3);
+    /// assert_eq!(second.keys(), 3..5);
+    /// assert_eq!(first.try_move_mut(4), Ok(None));
+    /// assert_eq!(second.try_move_mut(4), Ok(Some(&mut 4)));
+    /// ```
+    fn partition_many(&mut self, n: usize) -> Partitions<'_, T>;
+}
+
+impl<T> PartitionMany<T> for [T] {
+    fn partition_many(&mut self, n: usize) -> Partitions<'_, T> {
+        Partitions {
+            slice: self,
+            offset: 0,
+            n,
+        }
+    }
+}
+
+/// Iterator over disjoint views of the slice.
+///
+/// This struct is created by [`PartitionMany::partition_many`] method.
+#[derive(Debug)]
+pub struct Partitions<'s, T> {
+    slice: &'s mut [T],
+    offset: usize,
+    n: usize,
+}
+
+impl<'s, T> Iterator for Partitions<'s, T> {
+    type Item = Partition<'s, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.n == 0 {
+            return None;
+        }
+        let len = self.slice.len().div_ceil(self.n);
+        let (slice, rest) = mem::take(&mut self.slice).split_at_mut(len);
+        let partition = Partition {
+            slice,
+            offset: self.offset,
+        };
+
+        self.slice = rest;
+        self.offset += len;
+        self.n -= 1;
+        Some(partition)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.n, Some(self.n))
+    }
+}
+
+impl<T> ExactSizeIterator for Partitions<'_, T> {}
+
+impl<T> FusedIterator for Partitions<'_, T> {}
+
+/// View over the part of the slice which supports keyed moves
+/// by the indices of the original slice.
+///
+/// This struct is created by [`Partitions`] iterator.
+#[derive(Debug)]
+pub struct Partition<'s, T> {
+    slice: &'s mut [T],
+    offset: usize,
+}
+
+impl<'s, T> Partition<'s, T> {
+    /// Returns the range of indices of the original slice which are covered by this view.
+    pub fn keys(&self) -> Range<usize> {
+        self.offset..self.offset + self.slice.len()
+    }
+
+    /// Returns the part of the original slice, consuming the `self` value.
+    pub fn into_slice(self) -> &'s mut [T] {
+        self.slice
+    }
+
+    fn get_mut(&mut self, key: usize) -> Option<&mut T> {
+        let index = key.checked_sub(self.offset)?;
+        self.slice.get_mut(index)
+    }
+}
+
+/// Implementation of [`Many`] trait for [`Partition`].
+impl<'a, T> Many<'a, usize> for Partition<'_, T>
+where
+    T: Many<'a, usize>,
+{
+    type Ref = Option<T::Ref>;
+
+    fn try_move_ref(&mut self, key: usize) -> MoveResult<Self::Ref> {
+        let item = match self.get_mut(key) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let shared = item.try_move_ref(key)?;
+        Ok(Some(shared))
+    }
+
+    type Mut = Option<T::Mut>;
+
+    fn try_move_mut(&mut self, key: usize) -> MoveResult<Self::Mut> {
+        let item = match self.get_mut(key) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let unique = item.try_move_mut(key)?;
+        Ok(Some(unique))
+    }
+}
+
+/// Trait for slices of optional [`RefKind`]s which can be iterated by blocks of references.
+pub trait ChunksMany<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    /// Returns an iterator over disjoint chunks of `n` elements,
+    /// each of them yielding mutable references which are still available in the chunk.
+    ///
+    /// Mutable references are moved out of the slice as the chunk is iterated,
+    /// so elements which were not reached stay available.
+    /// The last chunk is shorter if the length of the slice is not divisible by `n`.
+    /// If `n` is zero, no chunks are returned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{partition::ChunksMany, Many, RefKind};
+    ///
+    /// let mut numbers = [0, 1, 2, 3, 4];
+    /// let mut many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+    /// many.try_move_ref(1).unwrap();
+    ///
+    /// for chunk in many.chunks_available_mut(2) {
+    ///     for (key, number) in chunk {
+    ///         *number += key * 10;
+    ///     }
+    /// }
+    /// assert_eq!(many.try_move_mut(0), Err(ref_kind::MoveError::BorrowedMutably));
+    /// drop(many);
+    /// assert_eq!(numbers, [0, 1, 22, 33, 44]);
+    /// ```
+    fn chunks_available_mut(&mut self, n: usize) -> ChunksAvailableMut<'_, 'a, T>;
+
+    /// Returns an iterator over overlapping windows of `N` immutable references.
+    ///
+    /// Mutable references of the window are replaced with immutable ones,
+    /// and elements which mutable references were already moved out are [`None`].
+    /// If the slice is shorter than `N` or `N` is zero, no windows are returned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{partition::ChunksMany, Many, RefKind};
+    ///
+    /// let mut numbers = [1, 2, 3, 4];
+    /// let mut many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+    /// many.try_move_mut(3).unwrap();
+    ///
+    /// let sums: Vec<i32> = many
+    ///     .windows_ref::<2>()
+    ///     .map(|window| window.into_iter().flatten().sum())
+    ///     .collect();
+    /// assert_eq!(sums, [3, 5, 3]);
+    /// assert!(many[0].as_ref().is_some_and(RefKind::is_ref));
+    /// ```
+    fn windows_ref<const N: usize>(&mut self) -> WindowsRef<'_, 'a, T, N>;
+
+    /// Tries to move mutable references of adjacent elements by keys `i` and `i + 1` out of the slice.
+    ///
+    /// Availability of both elements is checked before any reference is moved out,
+    /// so if one of them is unavailable, the slice is left untouched.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{partition::{AdjacentError, ChunksMany}, Many, MoveError, RefKind};
+    ///
+    /// let mut heights = [1, 4, 2];
+    /// let mut many = heights.each_mut().map(|i| Some(RefKind::from(i)));
+    ///
+    /// let (left, right) = many.try_move_adjacent_mut(0).unwrap();
+    /// let mean = (*left + *right) / 2;
+    /// (*left, *right) = (mean, mean);
+    ///
+    /// let error = many.try_move_adjacent_mut(1).unwrap_err();
+    /// assert_eq!(error, AdjacentError::Unavailable { key: 1, error: MoveError::BorrowedMutably });
+    /// assert_eq!(many.try_move_adjacent_mut(2), Err(AdjacentError::OutOfBounds));
+    /// assert_eq!(many.try_move_mut(2), Ok(Some(&mut 2)));
+    /// ```
+    fn try_move_adjacent_mut(&mut self, i: usize) -> Result<(&'a mut T, &'a mut T), AdjacentError>;
+
+    /// Returns an iterator over disjoint pairs of adjacent elements,
+    /// moving out mutable references of both elements of every pair.
+    ///
+    /// Pairs start at even keys, so the last element is skipped if the length of the slice is odd.
+    /// Pairs with an unavailable element are yielded as errors and left untouched,
+    /// in the same way as by [`try_move_adjacent_mut`](ChunksMany::try_move_adjacent_mut).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{partition::ChunksMany, Many, RefKind};
+    ///
+    /// let mut numbers = [1, 2, 3, 4, 5, 6, 7];
+    /// let mut many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+    /// many.try_move_ref(3).unwrap();
+    ///
+    /// for pair in many.pairs_mut() {
+    ///     if let Ok((first, second)) = pair {
+    ///         core::mem::swap(first, second);
+    ///     }
+    /// }
+    /// assert_eq!(numbers, [2, 1, 3, 4, 6, 5, 7]);
+    /// ```
+    fn pairs_mut(&mut self) -> PairsMut<'_, 'a, T>;
+}
+
+impl<'a, T> ChunksMany<'a, T> for [Option<RefKind<'a, T>>]
+where
+    T: ?Sized + 'a,
+{
+    fn chunks_available_mut(&mut self, n: usize) -> ChunksAvailableMut<'_, 'a, T> {
+        let slice = match n {
+            0 => &mut [],
+            _ => self,
+        };
+        ChunksAvailableMut {
+            chunks: slice.chunks_mut(n.max(1)),
+            offset: 0,
+        }
+    }
+
+    fn windows_ref<const N: usize>(&mut self) -> WindowsRef<'_, 'a, T, N> {
+        WindowsRef {
+            slice: self,
+            index: 0,
+        }
+    }
+
+    fn try_move_adjacent_mut(&mut self, i: usize) -> Result<(&'a mut T, &'a mut T), AdjacentError> {
+        let pair = i
+            .checked_add(2)
+            .and_then(|end| self.get_mut(i..end))
+            .ok_or(AdjacentError::OutOfBounds)?;
+        move_pair_mut(pair, i)
+    }
+
+    fn pairs_mut(&mut self) -> PairsMut<'_, 'a, T> {
+        PairsMut {
+            pairs: self.chunks_exact_mut(2),
+            offset: 0,
+        }
+    }
+}
+
+fn move_pair_mut<'a, T>(
+    pair: &mut [Option<RefKind<'a, T>>],
+    offset: usize,
+) -> Result<(&'a mut T, &'a mut T), AdjacentError>
+where
+    T: ?Sized + 'a,
+{
+    for (key, item) in (offset..).zip(pair.iter()) {
+        let error = match Availability::of(item) {
+            Availability::Mut => continue,
+            Availability::Ref => MoveError::BorrowedImmutably,
+            Availability::Moved => MoveError::BorrowedMutably,
+        };
+        return Err(AdjacentError::Unavailable { key, error });
+    }
+    let mut unique = pair.iter_mut().filter_map(|item| item.take()?.into_mut());
+    match (unique.next(), unique.next()) {
+        (Some(first), Some(second)) => Ok((first, second)),
+        _ => Err(AdjacentError::OutOfBounds),
+    }
+}
+
+/// Error which can occur when moving mutable references of adjacent elements out of the slice.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AdjacentError {
+    /// One of the adjacent elements is out of bounds of the slice.
+    OutOfBounds,
+    /// Mutable reference of one of the adjacent elements is not available.
+    Unavailable {
+        /// Key of the first unavailable element.
+        key: usize,
+        /// The reason why the reference is not available.
+        error: MoveError,
+    },
+}
+
+impl fmt::Display for AdjacentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "adjacent element is out of bounds"),
+            Self::Unavailable { key, error } => {
+                write!(f, "element by key {key} is unavailable: {error}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std_crate::error::Error for AdjacentError {}
+
+/// Iterator over disjoint pairs of adjacent elements of the slice of optional [`RefKind`]s.
+///
+/// This struct is created by [`ChunksMany::pairs_mut`] method.
+#[derive(Debug)]
+pub struct PairsMut<'s, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    pairs: slice::ChunksExactMut<'s, Option<RefKind<'a, T>>>,
+    offset: usize,
+}
+
+impl<'a, T> Iterator for PairsMut<'_, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    type Item = Result<(&'a mut T, &'a mut T), AdjacentError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pair = self.pairs.next()?;
+        let offset = self.offset;
+        self.offset += 2;
+        Some(move_pair_mut(pair, offset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pairs.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for PairsMut<'_, 'a, T> where T: ?Sized + 'a {}
+
+impl<'a, T> FusedIterator for PairsMut<'_, 'a, T> where T: ?Sized + 'a {}
+
+/// Iterator over disjoint chunks of the slice of optional [`RefKind`]s.
+///
+/// This struct is created by [`ChunksMany::chunks_available_mut`] method.
+#[derive(Debug)]
+pub struct ChunksAvailableMut<'s, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    chunks: slice::ChunksMut<'s, Option<RefKind<'a, T>>>,
+    offset: usize,
+}
+
+impl<'s, 'a, T> Iterator for ChunksAvailableMut<'s, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    type Item = AvailableMut<'s, 'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next()?;
+        let offset = self.offset;
+        self.offset += chunk.len();
+        Some(AvailableMut {
+            items: chunk.iter_mut(),
+            offset,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ChunksAvailableMut<'_, 'a, T> where T: ?Sized + 'a {}
+
+impl<'a, T> FusedIterator for ChunksAvailableMut<'_, 'a, T> where T: ?Sized + 'a {}
+
+/// Iterator over keys and mutable references which are still available in the chunk.
+///
+/// This struct is created by [`ChunksAvailableMut`] iterator.
+#[derive(Debug)]
+pub struct AvailableMut<'s, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    items: slice::IterMut<'s, Option<RefKind<'a, T>>>,
+    offset: usize,
+}
+
+impl<'a, T> Iterator for AvailableMut<'_, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    type Item = (usize, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.items.by_ref() {
+            let key = self.offset;
+            self.offset += 1;
+            if let Some(Mut(_)) = item {
+                let unique = item.take()?.into_mut()?;
+                return Some((key, unique));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.items.size_hint().1)
+    }
+}
+
+impl<'a, T> FusedIterator for AvailableMut<'_, 'a, T> where T: ?Sized + 'a {}
+
+/// Iterator over overlapping windows of immutable references of the slice of optional [`RefKind`]s.
+///
+/// This struct is created by [`ChunksMany::windows_ref`] method.
+#[derive(Debug)]
+pub struct WindowsRef<'s, 'a, T, const N: usize>
+where
+    T: ?Sized + 'a,
+{
+    slice: &'s mut [Option<RefKind<'a, T>>],
+    index: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for WindowsRef<'_, 'a, T, N>
+where
+    T: ?Sized + 'a,
+{
+    type Item = [Option<&'a T>; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if N == 0 {
+            return None;
+        }
+        let window = self.slice.get_mut(self.index..)?.get_mut(..N)?;
+        self.index += 1;
+        Some(array::from_fn(|i| {
+            let shared = window[i].take()?.into_ref();
+            window[i] = Some(Ref(shared));
+            Some(shared)
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match N {
+            0 => 0,
+            _ => (self.slice.len() + 1).saturating_sub(self.index + N),
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for WindowsRef<'_, 'a, T, N> where T: ?Sized + 'a {}
+
+impl<'a, T, const N: usize> FusedIterator for WindowsRef<'_, 'a, T, N> where T: ?Sized + 'a {}
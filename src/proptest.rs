@@ -0,0 +1,182 @@
+//! Provides [proptest] strategies for collections of [`RefKind`]
+//! and an operation model for stateful testing of [`Many`] implementations.
+//!
+//! References cannot be generated out of thin air, so strategies of this module
+//! generate *availability states*, which are then applied to the owner
+//! with [`apply`] to build a collection of optional [`RefKind`]s, one state per element of the owner.
+//!
+//! ## Example
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use ref_kind::proptest::{apply, availabilities, ops, Model};
+//!
+//! proptest!(|(states in availabilities(0..16), ops in ops(0..20usize, 0..32))| {
+//!     let mut owner = vec![0; states.len()];
+//!     let mut many = apply(&mut owner, &states);
+//!     let mut model = Model::new(states);
+//!
+//!     for op in ops {
+//!         let expected = model.step(op);
+//!         let actual = op.run(&mut many);
+//!         prop_assert_eq!(actual, expected);
+//!     }
+//! });
+//! ```
+//!
+//! [proptest]: https://docs.rs/proptest
+
+use alloc_crate::vec::Vec;
+use core::ops::Range;
+
+use proptest::{
+    collection::{self, SizeRange},
+    prelude::*,
+};
+
+pub use crate::Availability;
+
+use crate::{Many, MoveError, MoveResult, RefKind};
+
+impl Arbitrary for Availability {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        availability().boxed()
+    }
+}
+
+/// Strategy which generates any availability state.
+pub fn availability() -> impl Strategy<Value = Availability> {
+    prop_oneof![
+        Just(Availability::Mut),
+        Just(Availability::Ref),
+        Just(Availability::Moved),
+    ]
+}
+
+/// Strategy which generates availability states for collection of the provided size.
+pub fn availabilities(size: impl Into<SizeRange>) -> impl Strategy<Value = Vec<Availability>> {
+    collection::vec(availability(), size)
+}
+
+/// Builds a collection of optional [`RefKind`]s over the elements of the owner
+/// according to the provided availability states.
+///
+/// Each element of the owner must have its own state, so that [`Model`] created from the same states
+/// knows about every element of the collection.
+///
+/// # Panics
+///
+/// Panics if the count of states differs from the count of elements of the owner.
+#[track_caller]
+pub fn apply<'a, T>(owner: &'a mut [T], states: &[Availability]) -> Vec<Option<RefKind<'a, T>>> {
+    assert_eq!(
+        owner.len(),
+        states.len(),
+        "each element of the owner must have its own availability state",
+    );
+    owner
+        .iter_mut()
+        .zip(states)
+        .map(|(item, state)| match state {
+            Availability::Mut => Some(RefKind::Mut(item)),
+            Availability::Ref => Some(RefKind::Ref(item)),
+            Availability::Moved => None,
+        })
+        .collect()
+}
+
+/// Operation performed on the collection by key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Op<Key> {
+    /// Move an immutable reference out of the collection.
+    MoveRef(Key),
+    /// Move a mutable reference out of the collection.
+    MoveMut(Key),
+}
+
+impl Op<usize> {
+    /// Runs this operation on the collection built with [`apply`].
+    ///
+    /// Outcome is the same as the outcome of [`Model::step`] for the same operation.
+    pub fn run<'a, T>(self, many: &mut [Option<RefKind<'a, T>>]) -> MoveResult<bool>
+    where
+        T: ?Sized + 'a,
+    {
+        match self {
+            Self::MoveRef(key) => many.try_move_ref(key).map(|item| item.is_some()),
+            Self::MoveMut(key) => many.try_move_mut(key).map(|item| item.is_some()),
+        }
+    }
+}
+
+/// Strategy which generates any operation with the key generated by the provided strategy.
+pub fn op<K>(key: K) -> impl Strategy<Value = Op<K::Value>>
+where
+    K: Strategy,
+    K::Value: Clone,
+{
+    (any::<bool>(), key).prop_map(|(is_mut, key)| match is_mut {
+        true => Op::MoveMut(key),
+        false => Op::MoveRef(key),
+    })
+}
+
+/// Strategy which generates a sequence of operations with keys in the provided range.
+pub fn ops(
+    keys: Range<usize>,
+    size: impl Into<SizeRange>,
+) -> impl Strategy<Value = Vec<Op<usize>>> {
+    collection::vec(op(keys), size)
+}
+
+/// Reference model of the collection indexed by `usize` keys.
+///
+/// The model predicts the outcome of each operation
+/// performed on the collection built with [`apply`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Model {
+    states: Vec<Availability>,
+}
+
+impl Model {
+    /// Creates new model from the initial availability states.
+    pub fn new(states: Vec<Availability>) -> Self {
+        Self { states }
+    }
+
+    /// Returns current availability states of the model.
+    pub fn states(&self) -> &[Availability] {
+        &self.states
+    }
+
+    /// Performs an operation on the model, returning the expected outcome.
+    ///
+    /// Outcome is `Ok(true)` if a reference should be moved out,
+    /// `Ok(false)` if there is no element by the key or an error otherwise.
+    pub fn step(&mut self, op: Op<usize>) -> MoveResult<bool> {
+        let (key, is_mut) = match op {
+            Op::MoveRef(key) => (key, false),
+            Op::MoveMut(key) => (key, true),
+        };
+        let state = match self.states.get_mut(key) {
+            Some(state) => state,
+            None => return Ok(false),
+        };
+        match (*state, is_mut) {
+            (Availability::Moved, _) => Err(MoveError::BorrowedMutably),
+            (Availability::Ref, true) => Err(MoveError::BorrowedImmutably),
+            (Availability::Ref, false) => Ok(true),
+            (Availability::Mut, true) => {
+                *state = Availability::Moved;
+                Ok(true)
+            }
+            (Availability::Mut, false) => {
+                *state = Availability::Ref;
+                Ok(true)
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std_crate::collections::HashMap;
+
+use crate::{by_address::address, Many, MoveResult, RefKind, RefKindSparseSet};
+
+/// Wrapper of the [sparse set](RefKindSparseSet) which allows to give mutable references back by their address.
+///
+/// Wrapper maintains a secondary index from the address of every moved out mutable reference to its key,
+/// so [`give_back`](Rearmable::give_back) can re-arm the right entry in constant time
+/// without the caller tracking keys alongside every borrowed reference.
+///
+/// Values of zero-sized types share their addresses,
+/// so such references are given back into the entry which was moved out the latest.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Many, MoveError, RefKind, RefKindSparseSet, Rearmable};
+///
+/// let mut numbers = [0, 1, 2];
+/// let set: RefKindSparseSet<_> = numbers
+///     .iter_mut()
+///     .enumerate()
+///     .map(|(entity, number)| (entity * 10, Some(RefKind::from(number))))
+///     .collect();
+/// let mut many = Rearmable::new(set);
+///
+/// let number = many.try_move_mut(20).unwrap().unwrap();
+/// *number += 10;
+/// assert_eq!(many.try_move_ref(20), Err(MoveError::BorrowedMutably));
+///
+/// assert_eq!(many.give_back(number), Ok(20));
+/// assert_eq!(many.try_move_ref(20), Ok(Some(&12)));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Default)]
+pub struct Rearmable<'a, T>
+where
+    T: ?Sized,
+{
+    set: RefKindSparseSet<'a, T>,
+    moved: HashMap<usize, usize>,
+}
+
+impl<'a, T> Rearmable<'a, T>
+where
+    T: ?Sized,
+{
+    /// Creates new wrapper of the sparse set with no moved out references.
+    pub fn new(set: RefKindSparseSet<'a, T>) -> Self {
+        let moved = HashMap::new();
+        Self { set, moved }
+    }
+
+    /// Returns an immutable reference to the underlying sparse set.
+    pub fn get_ref(&self) -> &RefKindSparseSet<'a, T> {
+        &self.set
+    }
+
+    /// Gives mutable reference which was moved out of this wrapper back into its entry,
+    /// returning the key of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reference back if it was not moved out of this wrapper
+    /// or was already given back.
+    pub fn give_back(&mut self, value: &'a mut T) -> Result<usize, &'a mut T> {
+        let key = match self.moved.remove(&address(value)) {
+            Some(key) => key,
+            None => return Err(value),
+        };
+        self.set.insert(key, Some(RefKind::Mut(value)));
+        Ok(key)
+    }
+
+    /// Checks internal invariants of the wrapper in debug builds, panicking if some of them is violated.
+    ///
+    /// Besides [invariants of the sparse set](crate::SparseSet::debug_validate),
+    /// every address of the secondary index must point to the entry which mutable reference was moved out.
+    /// In release builds, this function does nothing.
+    #[track_caller]
+    pub fn debug_validate(&self) {
+        self.set.debug_validate();
+        for (&address, &key) in &self.moved {
+            debug_assert!(
+                matches!(self.set.get(key), Some(None)),
+                "address {address:#x} is indexed by key {key} which reference was not moved out",
+            );
+        }
+    }
+
+    /// Returns the underlying sparse set, consuming the `self` value.
+    pub fn into_inner(self) -> RefKindSparseSet<'a, T> {
+        self.set
+    }
+}
+
+impl<'a, T> From<RefKindSparseSet<'a, T>> for Rearmable<'a, T>
+where
+    T: ?Sized,
+{
+    fn from(set: RefKindSparseSet<'a, T>) -> Self {
+        Self::new(set)
+    }
+}
+
+/// Implementation of [`Many`] trait for [`Rearmable`] which records addresses of moved out mutable references.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<'a, T> Many<'a, usize> for Rearmable<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    type Ref = Option<&'a T>;
+
+    fn try_move_ref(&mut self, key: usize) -> MoveResult<Self::Ref> {
+        self.set.try_move_ref(key)
+    }
+
+    type Mut = Option<&'a mut T>;
+
+    fn try_move_mut(&mut self, key: usize) -> MoveResult<Self::Mut> {
+        let unique = self.set.try_move_mut(key)?;
+        if let Some(unique) = &unique {
+            self.moved.insert(address(*unique), key);
+        }
+        Ok(unique)
+    }
+}
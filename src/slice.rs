@@ -1,29 +1,37 @@
-use crate::{Many, Result};
+use crate::{Many, ManyIndex, MoveResult};
 
 /// Implementation of [`Many`] trait for [slice](prim@slice).
-impl<'a, T> Many<'a, usize> for [T]
+impl<'a, T, I> Many<'a, I> for [T]
 where
-    T: Many<'a, usize>,
+    I: ManyIndex<'a, T>,
 {
-    type Ref = Option<T::Ref>;
-
-    fn try_move_ref(&mut self, key: usize) -> Result<Self::Ref> {
-        let item = match self.get_mut(key) {
-            Some(item) => item,
-            None => return Ok(None),
-        };
-        let shared = item.try_move_ref(key)?;
-        Ok(Some(shared))
+    type Ref = I::Ref;
+
+    fn try_move_ref(&mut self, key: I) -> MoveResult<Self::Ref> {
+        key.try_move_ref(self)
+    }
+
+    type Mut = I::Mut;
+
+    fn try_move_mut(&mut self, key: I) -> MoveResult<Self::Mut> {
+        key.try_move_mut(self)
+    }
+}
+
+/// Implementation of [`Many`] trait for [array](prim@array).
+impl<'a, T, I, const N: usize> Many<'a, I> for [T; N]
+where
+    I: ManyIndex<'a, T>,
+{
+    type Ref = I::Ref;
+
+    fn try_move_ref(&mut self, key: I) -> MoveResult<Self::Ref> {
+        key.try_move_ref(self)
     }
 
-    type Mut = Option<T::Mut>;
+    type Mut = I::Mut;
 
-    fn try_move_mut(&mut self, key: usize) -> Result<Self::Mut> {
-        let item = match self.get_mut(key) {
-            Some(item) => item,
-            None => return Ok(None),
-        };
-        let unique = item.try_move_mut(key)?;
-        Ok(Some(unique))
+    fn try_move_mut(&mut self, key: I) -> MoveResult<Self::Mut> {
+        key.try_move_mut(self)
     }
 }
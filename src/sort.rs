@@ -0,0 +1,169 @@
+use alloc_crate::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{MoveError, MoveResult, RefKind};
+
+/// Trait for dense collections of optional references
+/// which can be sorted by the values the references point to.
+///
+/// Sorting is only valid while every reference is stored in the collection,
+/// because empty entries have no value to be compared with.
+/// Methods return the table which maps each old key into the new key of the entry,
+/// so keys stored elsewhere can be remapped.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Many, RefKind, SortMany};
+///
+/// let mut depths = [3, 1, 2];
+/// let mut queue: Vec<_> = depths.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+///
+/// let remap = queue.sort_unstable_by_pointee_key(|depth| *depth).unwrap();
+/// assert_eq!(remap, [2, 0, 1]);
+/// assert_eq!(queue.try_move_mut(0), Ok(Some(&mut 1)));
+///
+/// // Moved out entry cannot be compared
+/// assert!(queue.sort_by_pointee(|a, b| b.cmp(a)).is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait SortMany<T>
+where
+    T: ?Sized,
+{
+    /// Sorts entries by the values the references point to with the comparator function,
+    /// preserving the order of equal values.
+    ///
+    /// Returns the table which maps each old key into the new key of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MoveError::BorrowedMutably`] without reordering anything
+    /// if some of the references was already moved out of the collection.
+    fn sort_by_pointee<F>(&mut self, compare: F) -> MoveResult<Vec<usize>>
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Sorts entries by the key extracted from the values the references point to,
+    /// without preserving the order of equal values.
+    ///
+    /// Returns the table which maps each old key into the new key of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MoveError::BorrowedMutably`] without reordering anything
+    /// if some of the references was already moved out of the collection.
+    fn sort_unstable_by_pointee_key<K, F>(&mut self, f: F) -> MoveResult<Vec<usize>>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+}
+
+impl<'a, T> SortMany<T> for [Option<RefKind<'a, T>>]
+where
+    T: ?Sized + 'a,
+{
+    fn sort_by_pointee<F>(&mut self, mut compare: F) -> MoveResult<Vec<usize>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let pointees = pointees(self)?;
+        let mut order = Vec::from_iter(0..pointees.len());
+        order.sort_by(|&a, &b| compare(pointees[a], pointees[b]));
+        Ok(permute(self, order))
+    }
+
+    fn sort_unstable_by_pointee_key<K, F>(&mut self, mut f: F) -> MoveResult<Vec<usize>>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        let pointees = pointees(self)?;
+        let mut order = Vec::from_iter(0..pointees.len());
+        order.sort_unstable_by_key(|&index| f(pointees[index]));
+        Ok(permute(self, order))
+    }
+}
+
+/// Trait for dense collections of optional references
+/// which can remove duplicates by the values the references point to.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{DedupMany, RefKind};
+///
+/// let names = ["audio", "audio", "video"];
+/// let mut many: Vec<_> = names.iter().map(|i| Some(RefKind::from(i))).collect();
+/// // Both sources refer to the same value
+/// many.push(Some(RefKind::from(&names[2])));
+///
+/// let removed = many.dedup_by_pointee(|a, b| a == b);
+/// assert_eq!(removed, [1, 3]);
+/// assert_eq!(many.len(), 2);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait DedupMany<T>
+where
+    T: ?Sized,
+{
+    /// Removes consecutive entries which values are the same as the value of the previous entry,
+    /// returning the old keys of removed entries in ascending order.
+    ///
+    /// Closure receives the value of the entry and the value of the previous retained entry.
+    /// Use [`ptr::eq`](core::ptr::eq) to remove entries which point to the same address.
+    /// Empty entries are never removed and separate their neighbours.
+    fn dedup_by_pointee<F>(&mut self, same: F) -> Vec<usize>
+    where
+        F: FnMut(&T, &T) -> bool;
+}
+
+impl<'a, T> DedupMany<T> for Vec<Option<RefKind<'a, T>>>
+where
+    T: ?Sized + 'a,
+{
+    fn dedup_by_pointee<F>(&mut self, mut same: F) -> Vec<usize>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut removed = Vec::new();
+        let mut previous = None;
+        for (index, item) in self.iter().enumerate() {
+            let current = item.as_ref().map(RefKind::get_ref);
+            match (current, previous) {
+                (Some(current), Some(previous)) if same(current, previous) => removed.push(index),
+                _ => previous = current,
+            }
+        }
+
+        let mut removing = removed.iter().peekable();
+        let mut index = 0;
+        self.retain(|_| {
+            let retain = removing.next_if_eq(&&index).is_none();
+            index += 1;
+            retain
+        });
+        removed
+    }
+}
+
+fn pointees<'s, T>(items: &'s [Option<RefKind<'_, T>>]) -> MoveResult<Vec<&'s T>>
+where
+    T: ?Sized,
+{
+    items
+        .iter()
+        .map(|item| item.as_ref().map(RefKind::get_ref))
+        .collect::<Option<_>>()
+        .ok_or(MoveError::BorrowedMutably)
+}
+
+fn permute<T>(items: &mut [Option<T>], order: Vec<usize>) -> Vec<usize> {
+    let mut sorted: Vec<_> = order.iter().map(|&old| items[old].take()).collect();
+    let mut remap = Vec::from_iter(0..items.len());
+    for (new, old) in order.into_iter().enumerate() {
+        remap[old] = new;
+        items[new] = sorted[new].take();
+    }
+    remap
+}
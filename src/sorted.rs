@@ -0,0 +1,188 @@
+use alloc_crate::vec::Vec;
+use core::borrow::Borrow;
+
+use crate::{Many, MoveResult};
+
+/// Collection of entries which are kept sorted by key in a vector
+/// and looked up by binary search.
+///
+/// For read-mostly registries this uses less memory than hash maps or B-trees
+/// and does not need to hash keys, while still allowing arbitrary ordered keys.
+/// Insertion and removal take linear time, so the collection is best built at once
+/// from an iterator.
+///
+/// If keys are repeated, the last entry of the key is retained.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Many, MoveError, RefKind, SortedMany};
+///
+/// let (mut gravity, friction) = (9.8, 0.3);
+/// let mut many: SortedMany<_, _> = [
+///     ("gravity", Some(RefKind::from(&mut gravity))),
+///     ("friction", Some(RefKind::from(&friction))),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(many.keys().collect::<Vec<_>>(), [&"friction", &"gravity"]);
+/// assert_eq!(many.try_move_mut("gravity"), Ok(Some(&mut 9.8)));
+/// assert_eq!(many.try_move_mut("friction"), Err(MoveError::BorrowedImmutably));
+/// assert_eq!(many.try_move_ref("drag"), Ok(None));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SortedMany<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+impl<K, V> SortedMany<K, V> {
+    /// Creates new empty collection.
+    pub const fn new() -> Self {
+        let entries = Vec::new();
+        Self { entries }
+    }
+
+    /// Returns the count of entries in the collection.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if the collection contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the count of bytes allocated on the heap by the collection.
+    ///
+    /// This is an estimation based on the capacity of the vector of entries,
+    /// which does not include heap memory owned by the keys and values themselves.
+    pub fn allocated_bytes(&self) -> usize {
+        self.entries.capacity() * size_of::<(K, V)>()
+    }
+
+    /// Returns entries of the collection in the ascending order of keys.
+    pub fn entries(&self) -> &[(K, V)] {
+        &self.entries
+    }
+
+    /// Returns an iterator over keys of the collection in the ascending order.
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &K> {
+        self.entries.iter().map(|(key, _)| key)
+    }
+
+    /// Returns the underlying vector of entries sorted by key, consuming the `self` value.
+    pub fn into_inner(self) -> Vec<(K, V)> {
+        self.entries
+    }
+}
+
+impl<K, V> SortedMany<K, V>
+where
+    K: Ord,
+{
+    /// Returns an immutable reference to the value by the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        let index = self.search(key).ok()?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns a mutable reference to the value by the key.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        let index = self.search(key).ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Inserts the value by the key, returning the value which was stored before, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(index) => Some(core::mem::replace(&mut self.entries[index].1, value)),
+            Err(index) => {
+                self.entries.insert(index, (key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the value by the key, returning it if it was stored in the collection.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        let index = self.search(key).ok()?;
+        Some(self.entries.remove(index).1)
+    }
+
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.entries
+            .binary_search_by(|(probe, _)| probe.borrow().cmp(key))
+    }
+}
+
+impl<K, V> Default for SortedMany<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for SortedMany<K, V>
+where
+    K: Ord,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut entries: Vec<_> = iter.into_iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        // Retain the last entry of each key, as maps do
+        entries.reverse();
+        entries.dedup_by(|(a, _), (b, _)| a == b);
+        entries.reverse();
+        Self { entries }
+    }
+}
+
+/// Implementation of [`Many`] trait for [`SortedMany`] which looks up keys by binary search.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, K, V> Many<'a, K> for SortedMany<K, V>
+where
+    K: Ord,
+    V: Many<'a, K>,
+{
+    type Ref = Option<V::Ref>;
+
+    fn try_move_ref(&mut self, key: K) -> MoveResult<Self::Ref> {
+        let item = match self.get_mut(&key) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let shared = item.try_move_ref(key)?;
+        Ok(Some(shared))
+    }
+
+    type Mut = Option<V::Mut>;
+
+    fn try_move_mut(&mut self, key: K) -> MoveResult<Self::Mut> {
+        let item = match self.get_mut(&key) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let unique = item.try_move_mut(key)?;
+        Ok(Some(unique))
+    }
+}
@@ -0,0 +1,1027 @@
+//! Provides [`SparseSet`] collection keyed by entity ids
+//! and implementation of [`Many`] trait for this collection.
+
+use alloc_crate::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::{iter::FusedIterator, slice};
+#[cfg(feature = "std")]
+use std_crate::collections::HashMap;
+
+use crate::{
+    by_address::address, Availability, Many, ManyAvailability, MoveResult, Mut, Ref, RefKind,
+};
+
+/// Sparse set of optional [`RefKind`]s keyed by entity ids.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type RefKindSparseSet<'a, T> = SparseSet<Option<RefKind<'a, T>>>;
+
+/// Collection which stores values densely and maps keys to them through a sparse array of indices.
+///
+/// Lookup, insertion and removal by key take constant time,
+/// while iteration goes over the densely packed values only.
+/// This is the common storage layout for components keyed by entity ids.
+///
+/// Memory used by the sparse array grows with the largest key, so keys should be dense.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Many, RefKind, RefKindSparseSet};
+///
+/// let mut positions = [(0, 0), (1, 1), (2, 2)];
+/// let mut set: RefKindSparseSet<_> = positions
+///     .iter_mut()
+///     .zip([7, 3, 42])
+///     .map(|(position, entity)| (entity, Some(RefKind::from(position))))
+///     .collect();
+///
+/// let position = set.try_move_mut(42).unwrap().unwrap();
+/// position.0 += 10;
+/// assert_eq!(set.try_move_mut(5), Ok(None));
+///
+/// let available: Vec<_> = set
+///     .iter()
+///     .filter_map(|(entity, kind)| kind.as_ref().map(|_| entity))
+///     .collect();
+/// assert_eq!(available, [7, 3]);
+/// assert_eq!(positions[2], (12, 2));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone)]
+pub struct SparseSet<V> {
+    sparse: Vec<Option<usize>>,
+    keys: Vec<usize>,
+    values: Vec<V>,
+    /// Keys and addresses of references which were checked out of this set and not checked in yet.
+    checkouts: Vec<(usize, usize)>,
+}
+
+impl<V> SparseSet<V> {
+    /// Creates new empty sparse set.
+    pub const fn new() -> Self {
+        Self {
+            sparse: Vec::new(),
+            keys: Vec::new(),
+            values: Vec::new(),
+            checkouts: Vec::new(),
+        }
+    }
+
+    /// Creates new empty sparse set with space for at least `capacity` values.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            sparse: Vec::with_capacity(capacity),
+            keys: Vec::with_capacity(capacity),
+            values: Vec::with_capacity(capacity),
+            checkouts: Vec::new(),
+        }
+    }
+
+    /// Returns the count of values in the set.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if the set contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the count of bytes allocated on the heap by the set.
+    ///
+    /// This is an estimation based on the capacity of the sparse and dense arrays,
+    /// which does not include heap memory owned by the values themselves.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::SparseSet;
+    ///
+    /// let mut set = SparseSet::<u64>::new();
+    /// assert_eq!(set.allocated_bytes(), 0);
+    ///
+    /// set.insert(0, 42);
+    /// assert!(set.allocated_bytes() >= 2 * size_of::<usize>() + size_of::<u64>());
+    /// ```
+    pub fn allocated_bytes(&self) -> usize {
+        self.sparse.capacity() * size_of::<Option<usize>>()
+            + self.keys.capacity() * size_of::<usize>()
+            + self.values.capacity() * size_of::<V>()
+            + self.checkouts.capacity() * size_of::<(usize, usize)>()
+    }
+
+    /// Checks internal invariants of the set in debug builds, panicking if some of them is violated.
+    ///
+    /// Every key of the dense array must be mapped to its index through the sparse array,
+    /// and the counts of keys, values and mapped entries of the sparse array must match.
+    /// In release builds, this function does nothing.
+    #[track_caller]
+    pub fn debug_validate(&self) {
+        debug_assert_eq!(
+            self.keys.len(),
+            self.values.len(),
+            "counts of keys and values of the sparse set differ",
+        );
+        for (index, &key) in self.keys.iter().enumerate() {
+            debug_assert_eq!(
+                self.index(key),
+                Some(index),
+                "key {key} of the sparse set is not mapped to its dense index",
+            );
+        }
+        debug_assert_eq!(
+            self.sparse.iter().flatten().count(),
+            self.keys.len(),
+            "sparse array of the sparse set maps keys which are not stored",
+        );
+    }
+
+    /// Checks if the set contains value by the key.
+    pub fn contains(&self, key: usize) -> bool {
+        self.index(key).is_some()
+    }
+
+    /// Returns an immutable reference to the value by the key.
+    pub fn get(&self, key: usize) -> Option<&V> {
+        let index = self.index(key)?;
+        self.values.get(index)
+    }
+
+    /// Returns a mutable reference to the value by the key.
+    pub fn get_mut(&mut self, key: usize) -> Option<&mut V> {
+        let index = self.index(key)?;
+        self.values.get_mut(index)
+    }
+
+    /// Inserts the value by the key, returning the value which was stored before, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sparse array cannot grow to hold the key, e.g. if the key is [`usize::MAX`].
+    #[track_caller]
+    pub fn insert(&mut self, key: usize, value: V) -> Option<V> {
+        self.forget_checkout(key);
+        if let Some(index) = self.index(key) {
+            return Some(core::mem::replace(&mut self.values[index], value));
+        }
+        if self.sparse.len() <= key {
+            let len = key
+                .checked_add(1)
+                .expect("key of the sparse set is too large");
+            self.sparse.resize(len, None);
+        }
+        self.sparse[key] = Some(self.values.len());
+        self.keys.push(key);
+        self.values.push(value);
+        None
+    }
+
+    /// Removes the value by the key, returning it if it was stored in the set.
+    ///
+    /// The last value of the dense array takes place of the removed one,
+    /// so the order of iteration changes.
+    pub fn remove(&mut self, key: usize) -> Option<V> {
+        let index = self.index(key)?;
+        self.forget_checkout(key);
+        self.sparse[key] = None;
+        self.keys.swap_remove(index);
+        let value = self.values.swap_remove(index);
+        if let Some(&moved) = self.keys.get(index) {
+            self.sparse[moved] = Some(index);
+        }
+        Some(value)
+    }
+
+    /// Removes all values from the set.
+    pub fn clear(&mut self) {
+        self.sparse.clear();
+        self.keys.clear();
+        self.values.clear();
+        self.checkouts.clear();
+    }
+
+    /// Returns keys of the set in the order of iteration.
+    pub fn keys(&self) -> &[usize] {
+        &self.keys
+    }
+
+    /// Returns values of the set in the order of iteration.
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Returns mutable values of the set in the order of iteration.
+    pub fn values_mut(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+
+    /// Returns an iterator over keys and values of the set.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            keys: self.keys.iter(),
+            values: self.values.iter(),
+        }
+    }
+
+    /// Returns an iterator over keys and mutable values of the set.
+    pub fn iter_mut(&mut self) -> IterMut<'_, V> {
+        IterMut {
+            keys: self.keys.iter(),
+            values: self.values.iter_mut(),
+        }
+    }
+
+    /// Moves values which match the predicate into a new set, leaving the rest of values in this set.
+    ///
+    /// Predicate receives the key and the value.
+    /// Values of both sets keep their relative order of iteration.
+    /// If the predicate panics, this set is left unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1, 2, 3];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    ///
+    /// let mut odd = set.split_off_with(|entity, _| entity % 2 == 1);
+    /// assert_eq!(set.keys(), [0, 2]);
+    /// assert_eq!(odd.keys(), [1, 3]);
+    /// assert_eq!(odd.try_move_mut(3), Ok(Some(&mut 3)));
+    /// assert_eq!(odd.try_move_mut(2), Ok(None));
+    /// ```
+    pub fn split_off_with<F>(&mut self, mut pred: F) -> Self
+    where
+        F: FnMut(usize, &V) -> bool,
+    {
+        // Predicate is called before the set is modified, so the set stays intact if it panics.
+        let matches: Vec<_> = self.iter().map(|(key, value)| pred(key, value)).collect();
+
+        let keys = core::mem::take(&mut self.keys);
+        let values = core::mem::take(&mut self.values);
+        let mut other = Self::new();
+        for ((key, value), matches) in keys.into_iter().zip(values).zip(matches) {
+            self.sparse[key] = None;
+            match matches {
+                true => other.insert(key, value),
+                false => self.insert(key, value),
+            };
+        }
+        other
+    }
+
+    /// Partitions values of the set into per-group sets, consuming the `self` value.
+    ///
+    /// Group function receives the key and the value.
+    /// Values of each group keep their relative order of iteration.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut meshes = [("stone", 0), ("glass", 1), ("stone", 2)];
+    /// let set: RefKindSparseSet<_> = meshes
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, mesh)| (entity, Some(RefKind::from(mesh))))
+    ///     .collect();
+    ///
+    /// let mut groups = set.group_by(|_, mesh| mesh.as_deref().map(|mesh| mesh.0));
+    /// let stone = groups.get_mut(&Some("stone")).unwrap();
+    /// assert_eq!(stone.keys(), [0, 2]);
+    /// assert_eq!(stone.try_move_mut(2), Ok(Some(&mut ("stone", 2))));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn group_by<G, F>(self, mut f: F) -> HashMap<G, Self>
+    where
+        G: Eq + Hash,
+        F: FnMut(usize, &V) -> G,
+    {
+        let mut groups = HashMap::<G, Self>::new();
+        for (key, value) in self.keys.into_iter().zip(self.values) {
+            let group = f(key, &value);
+            groups.entry(group).or_default().insert(key, value);
+        }
+        groups
+    }
+
+    /// Merges values of the other set into this set, consuming both of them.
+    ///
+    /// Values by the keys which are stored in one of the sets only are moved into the result as is.
+    /// For each key stored in both sets, the policy receives the key with the value of this set
+    /// and the value of the other set, and chooses the value to be stored in the result
+    /// or rejects the merge with an error.
+    ///
+    /// Chosen values are moved to the end of the dense array, so the order of iteration changes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{MoveError, RefKind, RefKindSparseSet};
+    ///
+    /// let (mut player, mut enemy, mut item) = ((0, 0), (5, 5), (9, 9));
+    /// let physics = RefKindSparseSet::from_iter([
+    ///     (0, Some(RefKind::from(&mut player))),
+    ///     (1, Some(RefKind::from(&mut enemy))),
+    /// ]);
+    /// let loot = RefKindSparseSet::from_iter([(1, None), (2, Some(RefKind::from(&mut item)))]);
+    ///
+    /// // Keep the availability of the left set for duplicate keys
+    /// let merged = physics.merge(loot, |_, left, _| Ok::<_, ()>(left)).unwrap();
+    /// assert_eq!(merged.keys(), [0, 1, 2]);
+    ///
+    /// // Reject duplicate keys
+    /// let other = RefKindSparseSet::from_iter([(1, Some(RefKind::from(&(1, 1))))]);
+    /// let error = merged.merge(other, |_, _, _| Err(MoveError::BorrowedMutably));
+    /// assert_eq!(error.err(), Some(MoveError::BorrowedMutably));
+    /// ```
+    pub fn merge<F, E>(mut self, other: Self, mut policy: F) -> Result<Self, E>
+    where
+        F: FnMut(usize, V, V) -> Result<V, E>,
+    {
+        for (key, value) in other.keys.into_iter().zip(other.values) {
+            let value = match self.remove(key) {
+                Some(left) => policy(key, left, value)?,
+                None => value,
+            };
+            self.insert(key, value);
+        }
+        Ok(self)
+    }
+
+    /// Maps every value of the set to the value of another type, keeping the keys and the order of iteration.
+    ///
+    /// Together with [`RefKind::map`] this allows to project every stored reference
+    /// to the field of the value, preserving the kinds of references and their availability.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut position = (0, 0);
+    /// let set = RefKindSparseSet::from_iter([(3, Some(RefKind::from(&mut position))), (7, None)]);
+    ///
+    /// let mut xs = set.map_values(|kind| kind.map(|kind| kind.map(|p| &p.0, |p| &mut p.0)));
+    /// assert_eq!(xs.try_move_mut(3), Ok(Some(&mut 0)));
+    /// assert!(xs.try_move_mut(7).is_err());
+    /// ```
+    pub fn map_values<U, F>(self, f: F) -> SparseSet<U>
+    where
+        F: FnMut(V) -> U,
+    {
+        let Self {
+            sparse,
+            keys,
+            values,
+            ..
+        } = self;
+        let values = values.into_iter().map(f).collect();
+        SparseSet {
+            sparse,
+            keys,
+            values,
+            checkouts: Vec::new(),
+        }
+    }
+
+    fn index(&self, key: usize) -> Option<usize> {
+        self.sparse.get(key).copied().flatten()
+    }
+
+    fn forget_checkout(&mut self, key: usize) {
+        self.checkouts
+            .retain(|&(checked_out, _)| checked_out != key);
+    }
+}
+
+impl<'a, T> SparseSet<Option<RefKind<'a, T>>>
+where
+    T: ?Sized + 'a,
+{
+    /// Returns an iterator over keys which mutable references are still available.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1, 2];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    /// set.try_move_ref(1).unwrap();
+    /// set.try_move_mut(2).unwrap();
+    ///
+    /// assert!(set.keys_mut_available().eq([0]));
+    /// assert!(set.keys_ref_only().eq([1]));
+    /// assert!(set.keys_taken().eq([2]));
+    /// ```
+    pub fn keys_mut_available(&self) -> KeysBy<'_, 'a, T> {
+        self.keys_by(Availability::Mut)
+    }
+
+    /// Returns an iterator over keys which hold immutable references only.
+    pub fn keys_ref_only(&self) -> KeysBy<'_, 'a, T> {
+        self.keys_by(Availability::Ref)
+    }
+
+    /// Returns an iterator over keys which mutable references were already moved out.
+    pub fn keys_taken(&self) -> KeysBy<'_, 'a, T> {
+        self.keys_by(Availability::Moved)
+    }
+
+    /// Returns an iterator over values which mutable references are still available.
+    ///
+    /// Values are reborrowed through the stored mutable references rather than moved out,
+    /// so the references stay available after the iteration.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1, 2];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    /// set.try_move_ref(0).unwrap();
+    ///
+    /// for number in set.values_available_mut() {
+    ///     *number *= 10;
+    /// }
+    /// assert_eq!(set.try_move_mut(2), Ok(Some(&mut 20)));
+    /// ```
+    pub fn values_available_mut(&mut self) -> ValuesAvailableMut<'_, 'a, T> {
+        let values = self.values.iter_mut();
+        ValuesAvailableMut { values }
+    }
+
+    /// Calls the closure with the key and the value of every entry which mutable reference is still available.
+    ///
+    /// Values are reborrowed in the same way as by [`values_available_mut`](Self::values_available_mut),
+    /// so the references stay available after the call.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1, 2];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    /// set.try_move_ref(0).unwrap();
+    ///
+    /// set.for_each_available_mut(|entity, number| *number += entity * 10);
+    /// assert_eq!(set.try_move_mut(2), Ok(Some(&mut 22)));
+    /// ```
+    pub fn for_each_available_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &mut T),
+    {
+        for (key, kind) in self.iter_mut() {
+            if let Some(Mut(unique)) = kind {
+                f(key, unique);
+            }
+        }
+    }
+
+    /// Calls the fallible closure with the key and the value of every entry which mutable reference is still available,
+    /// stopping at the first error.
+    ///
+    /// Returns the first error together with the key of the entry it occurred on.
+    /// References stay available after the call, see [`for_each_available_mut`](Self::for_each_available_mut).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [1, 0, 2];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    ///
+    /// let result = set.try_for_each_available_mut(|_, number| match *number {
+    ///     0 => Err("zero divisor"),
+    ///     _ => Ok(*number = 10 / *number),
+    /// });
+    /// assert_eq!(result, Err((1, "zero divisor")));
+    /// assert_eq!(numbers, [10, 0, 2]);
+    /// ```
+    pub fn try_for_each_available_mut<F, E>(&mut self, mut f: F) -> Result<(), (usize, E)>
+    where
+        F: FnMut(usize, &mut T) -> Result<(), E>,
+    {
+        self.iter_mut().try_for_each(|(key, kind)| match kind {
+            Some(Mut(unique)) => f(key, unique).map_err(|error| (key, error)),
+            _ => Ok(()),
+        })
+    }
+
+    /// Folds the key and the value of every entry which reference is still stored into an accumulator.
+    ///
+    /// Both mutable and immutable references are visited as immutable ones,
+    /// so aggregates can be computed without collecting references first.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [1, 2, 3];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    /// set.try_move_ref(0).unwrap();
+    /// set.try_move_mut(2).unwrap();
+    ///
+    /// let sum = set.fold_available(0, |sum, _, number| sum + number);
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn fold_available<B, F>(&self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, usize, &T) -> B,
+    {
+        self.iter().fold(init, |acc, (key, kind)| match kind {
+            Some(kind) => f(acc, key, kind.get_ref()),
+            None => acc,
+        })
+    }
+
+    /// Downgrades every stored mutable reference into immutable one in place.
+    ///
+    /// After this call immutable references can be moved out by every key which reference was not moved out yet.
+    /// This is useful for transitions from the write phase into the read phase
+    /// without rebuilding the set or downgrading references key by key.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, MoveError, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1, 2];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    /// *set.try_move_mut(2).unwrap().unwrap() += 10;
+    ///
+    /// set.downgrade_all();
+    /// assert_eq!(set.try_move_mut(0), Err(MoveError::BorrowedImmutably));
+    /// assert_eq!(set.try_move_ref(0), Ok(Some(&0)));
+    /// assert_eq!(set.try_move_ref(0), Ok(Some(&0)));
+    /// assert_eq!(set.try_move_ref(2), Err(MoveError::BorrowedMutably));
+    /// ```
+    pub fn downgrade_all(&mut self) {
+        for item in &mut self.values {
+            if let Some(Mut(_)) = item {
+                *item = item.take().map(|kind| Ref(kind.into_ref()));
+            }
+        }
+    }
+
+    /// Splits the set into the set of mutable references and the set of immutable references
+    /// by the current kind of each stored reference, consuming the `self` value.
+    ///
+    /// Keys which references were already moved out are dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1, 2];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    /// set.try_move_ref(1).unwrap();
+    /// set.try_move_mut(2).unwrap();
+    ///
+    /// let (writable, readable) = set.partition_kinds();
+    /// assert_eq!(writable.keys(), [0]);
+    /// assert_eq!(readable.get(1), Some(&&1));
+    /// ```
+    pub fn partition_kinds(self) -> (SparseSet<&'a mut T>, SparseSet<&'a T>) {
+        let mut unique = SparseSet::new();
+        let mut shared = SparseSet::new();
+        for (key, kind) in self.keys.into_iter().zip(self.values) {
+            match kind {
+                Some(Ref(kind)) => {
+                    shared.insert(key, kind);
+                }
+                Some(Mut(kind)) => {
+                    unique.insert(key, kind);
+                }
+                None => {}
+            }
+        }
+        (unique, shared)
+    }
+
+    /// Creates a speculative copy of the set which reborrows every stored reference.
+    ///
+    /// References moved out of the speculative set do not change availability of this set,
+    /// and this set cannot be used while the speculative set or any reference moved out of it is alive.
+    /// So if the speculative phase is aborted, dropping the speculative set rolls back
+    /// "who has claimed what" soundly; to commit it, repeat the same moves on this set.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, MoveError, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    ///
+    /// {
+    ///     let mut speculative = set.speculate();
+    ///     *speculative.try_move_mut(0).unwrap().unwrap() += 10;
+    ///     // Conflict detected, the speculative phase is aborted
+    ///     assert_eq!(speculative.try_move_ref(0), Err(MoveError::BorrowedMutably));
+    /// }
+    ///
+    /// assert_eq!(set.try_move_mut(0), Ok(Some(&mut 10)));
+    /// ```
+    pub fn speculate(&mut self) -> SparseSet<Option<RefKind<'_, T>>> {
+        let values = self
+            .values
+            .iter_mut()
+            .map(|kind| kind.as_mut().map(RefKind::reborrow))
+            .collect();
+        SparseSet {
+            sparse: self.sparse.clone(),
+            keys: self.keys.clone(),
+            values,
+            checkouts: Vec::new(),
+        }
+    }
+
+    /// Returns the key which stored reference points to the provided value.
+    ///
+    /// References are compared by address, not by value.
+    /// Mutable references which were already moved out are not stored in the set anymore,
+    /// so they cannot be found.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [7, 7];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity * 10, Some(RefKind::from(number))))
+    ///     .collect();
+    ///
+    /// let shared = set.try_move_ref(10).unwrap().unwrap();
+    /// assert_eq!(set.find_key_of(shared), Some(10));
+    /// assert_eq!(set.find_key_of(&7), None);
+    /// ```
+    pub fn find_key_of(&self, value: &T) -> Option<usize> {
+        self.iter()
+            .find(|(_, kind)| matches!(kind, Some(kind) if core::ptr::eq(kind.get_ref(), value)))
+            .map(|(key, _)| key)
+    }
+
+    /// Moves mutable reference out of the entry by the key together with the token of this checkout.
+    ///
+    /// The token is the capability to [check in](SparseSet::check_in) the reference later.
+    /// Unlike a guard, it does not borrow the sparse set, so it can be stored or routed
+    /// through a schedule and checked in at a controlled point.
+    /// In debug builds, dropping the token without checking it in panics:
+    /// use [`ClaimToken::forget`] to keep the reference moved out on purpose.
+    ///
+    /// Returns [`None`] if there is no entry by the key.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, MoveError, RefKind, RefKindSparseSet};
+    ///
+    /// let mut numbers = [0, 1];
+    /// let mut set: RefKindSparseSet<_> = numbers
+    ///     .iter_mut()
+    ///     .enumerate()
+    ///     .map(|(entity, number)| (entity, Some(RefKind::from(number))))
+    ///     .collect();
+    ///
+    /// let (number, token) = set.checkout_mut(1).unwrap().unwrap();
+    /// *number += 10;
+    /// assert_eq!(set.try_move_ref(1), Err(MoveError::BorrowedMutably));
+    ///
+    /// assert_eq!(set.check_in(token, number), Ok(1));
+    /// assert_eq!(set.try_move_ref(1), Ok(Some(&11)));
+    /// ```
+    pub fn checkout_mut(&mut self, key: usize) -> MoveResult<Option<(&'a mut T, ClaimToken)>> {
+        let unique = match self.try_move_mut(key)? {
+            Some(unique) => unique,
+            None => return Ok(None),
+        };
+        let address = address(unique);
+        self.forget_checkout(key);
+        self.checkouts.push((key, address));
+        Ok(Some((unique, ClaimToken { key, address })))
+    }
+
+    /// Gives the checked out mutable reference back into its entry, consuming the token of the checkout.
+    /// Returns the key of the entry.
+    ///
+    /// Safe code cannot recreate a mutable reference which was moved out,
+    /// so the reference has to be returned along with the token.
+    ///
+    /// # Errors
+    ///
+    /// Returns both the token and the reference back if the reference was not checked out with this token,
+    /// if the token was issued by another set,
+    /// or if the entry of the token was removed or refilled in the meantime.
+    pub fn check_in(
+        &mut self,
+        token: ClaimToken,
+        value: &'a mut T,
+    ) -> Result<usize, (ClaimToken, &'a mut T)> {
+        if token.address != address(value) {
+            return Err((token, value));
+        }
+        // Set remembers its own checkouts, so tokens of other sets are rejected
+        let checkout = (token.key, token.address);
+        let position = match self.checkouts.iter().position(|&c| c == checkout) {
+            Some(position) => position,
+            None => return Err((token, value)),
+        };
+        let item = match self.get_mut(token.key) {
+            Some(item @ None) => item,
+            _ => return Err((token, value)),
+        };
+        *item = Some(RefKind::Mut(value));
+        self.checkouts.swap_remove(position);
+        Ok(token.forget())
+    }
+
+    fn keys_by(&self, state: Availability) -> KeysBy<'_, 'a, T> {
+        let iter = self.iter();
+        KeysBy { iter, state }
+    }
+}
+
+impl<V> Default for SparseSet<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> Extend<(usize, V)> for SparseSet<V> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (usize, V)>,
+    {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<V> FromIterator<(usize, V)> for SparseSet<V> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, V)>,
+    {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'s, V> IntoIterator for &'s SparseSet<V> {
+    type Item = (usize, &'s V);
+    type IntoIter = Iter<'s, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'s, V> IntoIterator for &'s mut SparseSet<V> {
+    type Item = (usize, &'s mut V);
+    type IntoIter = IterMut<'s, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Iterator over keys and values of the [`SparseSet`].
+///
+/// This struct is created by [`SparseSet::iter`] method.
+#[derive(Debug, Clone)]
+pub struct Iter<'s, V> {
+    keys: slice::Iter<'s, usize>,
+    values: slice::Iter<'s, V>,
+}
+
+impl<'s, V> Iterator for Iter<'s, V> {
+    type Item = (usize, &'s V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((*self.keys.next()?, self.values.next()?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<V> ExactSizeIterator for Iter<'_, V> {}
+
+impl<V> FusedIterator for Iter<'_, V> {}
+
+/// Iterator over keys and mutable values of the [`SparseSet`].
+///
+/// This struct is created by [`SparseSet::iter_mut`] method.
+#[derive(Debug)]
+pub struct IterMut<'s, V> {
+    keys: slice::Iter<'s, usize>,
+    values: slice::IterMut<'s, V>,
+}
+
+impl<'s, V> Iterator for IterMut<'s, V> {
+    type Item = (usize, &'s mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((*self.keys.next()?, self.values.next()?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<V> ExactSizeIterator for IterMut<'_, V> {}
+
+impl<V> FusedIterator for IterMut<'_, V> {}
+
+/// Iterator over keys of the [`RefKindSparseSet`] filtered by availability of their references.
+///
+/// This struct is created by [`SparseSet::keys_mut_available`],
+/// [`SparseSet::keys_ref_only`] and [`SparseSet::keys_taken`] methods.
+#[derive(Debug)]
+pub struct KeysBy<'s, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    iter: Iter<'s, Option<RefKind<'a, T>>>,
+    state: Availability,
+}
+
+impl<'a, T> Iterator for KeysBy<'_, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = self.state;
+        self.iter
+            .find_map(|(key, kind)| (Availability::of(kind) == state).then_some(key))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, T> FusedIterator for KeysBy<'_, 'a, T> where T: ?Sized + 'a {}
+
+/// Iterator over values of the [`RefKindSparseSet`] which mutable references are still available.
+///
+/// This struct is created by [`SparseSet::values_available_mut`] method.
+#[derive(Debug)]
+pub struct ValuesAvailableMut<'s, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    values: slice::IterMut<'s, Option<RefKind<'a, T>>>,
+}
+
+impl<'s, 'a, T> Iterator for ValuesAvailableMut<'s, 'a, T>
+where
+    T: ?Sized + 'a,
+{
+    type Item = &'s mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.find_map(|kind| kind.as_mut()?.get_mut())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.values.size_hint().1)
+    }
+}
+
+impl<'a, T> FusedIterator for ValuesAvailableMut<'_, 'a, T> where T: ?Sized + 'a {}
+
+/// Token of the mutable reference checked out of the [`RefKindSparseSet`].
+///
+/// This struct is created by [`SparseSet::checkout_mut`] method
+/// and consumed by [`SparseSet::check_in`] method.
+#[must_use = "token must be checked in to make the entry available again"]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ClaimToken {
+    key: usize,
+    address: usize,
+}
+
+impl ClaimToken {
+    /// Returns the key of the entry which reference was checked out.
+    pub fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Discards the token without checking it in, returning the key of the entry.
+    ///
+    /// The entry stays unavailable until it is refilled.
+    pub fn forget(self) -> usize {
+        let key = self.key;
+        core::mem::forget(self);
+        key
+    }
+}
+
+impl Drop for ClaimToken {
+    fn drop(&mut self) {
+        #[cfg(all(debug_assertions, feature = "std"))]
+        if !std_crate::thread::panicking() {
+            panic!(
+                "claim token of key {} was leaked without check-in",
+                self.key
+            );
+        }
+    }
+}
+
+/// Implementation of [`Many`] trait for [`SparseSet`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, V> Many<'a, usize> for SparseSet<V>
+where
+    V: Many<'a, usize>,
+{
+    type Ref = Option<V::Ref>;
+
+    fn try_move_ref(&mut self, key: usize) -> MoveResult<Self::Ref> {
+        let item = match self.get_mut(key) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let shared = item.try_move_ref(key)?;
+        Ok(Some(shared))
+    }
+
+    type Mut = Option<V::Mut>;
+
+    fn try_move_mut(&mut self, key: usize) -> MoveResult<Self::Mut> {
+        let item = match self.get_mut(key) {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let unique = item.try_move_mut(key)?;
+        Ok(Some(unique))
+    }
+}
+
+/// Implementation of [`ManyAvailability`] trait for [`SparseSet`].
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<V> ManyAvailability<usize> for SparseSet<V>
+where
+    V: ManyAvailability<usize>,
+{
+    fn availability(&self, key: &usize) -> Option<Availability> {
+        self.get(*key)?.availability(key)
+    }
+}
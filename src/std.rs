@@ -1,7 +1,7 @@
 use core::hash::{BuildHasher, Hash};
 use std_crate::collections::HashMap;
 
-use crate::{Many, Result};
+use crate::{Availability, Many, ManyAvailability, MoveResult};
 
 /// Implementation of [`Many`] trait for [`HashMap`].
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
@@ -13,7 +13,7 @@ where
 {
     type Ref = Option<V::Ref>;
 
-    fn try_move_ref(&mut self, key: K) -> Result<Self::Ref> {
+    fn try_move_ref(&mut self, key: K) -> MoveResult<Self::Ref> {
         let item = match self.get_mut(&key) {
             Some(item) => item,
             None => return Ok(None),
@@ -24,7 +24,7 @@ where
 
     type Mut = Option<V::Mut>;
 
-    fn try_move_mut(&mut self, key: K) -> Result<Self::Mut> {
+    fn try_move_mut(&mut self, key: K) -> MoveResult<Self::Mut> {
         let item = match self.get_mut(&key) {
             Some(item) => item,
             None => return Ok(None),
@@ -33,3 +33,16 @@ where
         Ok(Some(unique))
     }
 }
+
+/// Implementation of [`ManyAvailability`] trait for [`HashMap`].
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<K, V, S> ManyAvailability<K> for HashMap<K, V, S>
+where
+    K: Hash + Eq,
+    V: ManyAvailability<K>,
+    S: BuildHasher,
+{
+    fn availability(&self, key: &K) -> Option<Availability> {
+        self.get(key)?.availability(key)
+    }
+}
@@ -0,0 +1,151 @@
+//! Lock-free storage of [`AtomicRefKind`](super::AtomicRefKind)
+//! which keeps the reference as a raw pointer guarded by the atomic state word.
+
+#![allow(unsafe_code)]
+
+use core::{fmt, marker::PhantomData, ptr::NonNull};
+
+use crate::{Availability, MoveError, MoveResult, Mut, Ref, RefKind};
+
+use super::super::primitives::{AtomicU8, Ordering, UnsafeCell};
+
+/// Mutable reference is stored in the cell.
+const MUT: u8 = 0;
+/// Immutable reference is stored in the cell.
+const REF: u8 = 1;
+/// Mutable reference was moved out of the cell.
+const MOVED: u8 = 2;
+/// Mutable reference is being moved out of the cell.
+const TAKING: u8 = 3;
+/// Mutable reference is being given back into the cell.
+const GIVING: u8 = 4;
+
+pub(super) struct KindCell<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    state: AtomicU8,
+    pointer: UnsafeCell<Option<NonNull<T>>>,
+    marker: PhantomData<RefKind<'a, T>>,
+}
+
+// SAFETY: the cell hands out the reference either as mutable to exactly one thread
+// or as immutable to any number of threads, in the same way as `Mutex<Option<RefKind<'a, T>>>` does
+unsafe impl<'a, T> Send for KindCell<'a, T> where T: ?Sized + Send + Sync + 'a {}
+
+// SAFETY: the cell hands out the reference either as mutable to exactly one thread
+// or as immutable to any number of threads, in the same way as `Mutex<Option<RefKind<'a, T>>>` does
+unsafe impl<'a, T> Sync for KindCell<'a, T> where T: ?Sized + Send + Sync + 'a {}
+
+impl<'a, T> KindCell<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    pub(super) fn new(kind: Option<RefKind<'a, T>>) -> Self {
+        let (state, pointer) = match kind {
+            Some(Ref(shared)) => (REF, Some(NonNull::from(shared))),
+            Some(Mut(unique)) => (MUT, Some(NonNull::from(unique))),
+            None => (MOVED, None),
+        };
+        Self {
+            state: AtomicU8::new(state),
+            pointer: UnsafeCell::new(pointer),
+            marker: PhantomData,
+        }
+    }
+
+    pub(super) fn availability(&self) -> Availability {
+        match self.state.load(Ordering::Acquire) {
+            MUT => Availability::Mut,
+            REF => Availability::Ref,
+            _ => Availability::Moved,
+        }
+    }
+
+    pub(super) fn try_move_ref(&self) -> MoveResult<&'a T> {
+        match self
+            .state
+            .compare_exchange(MUT, REF, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) | Err(REF) => {
+                // SAFETY: the pointer is written only in `MOVED` and `GIVING` states,
+                // and the state cannot leave `REF` until the cell is consumed
+                let pointer = self.pointer.with(|pointer| unsafe { *pointer });
+                let Some(pointer) = pointer else {
+                    return Err(MoveError::BorrowedMutably);
+                };
+                // SAFETY: the pointer was created from the reference of lifetime `'a`,
+                // and the state is `REF`, so no mutable reference can be handed out anymore
+                Ok(unsafe { pointer.as_ref() })
+            }
+            Err(_) => Err(MoveError::BorrowedMutably),
+        }
+    }
+
+    pub(super) fn try_move_mut(&self) -> MoveResult<&'a mut T> {
+        match self
+            .state
+            .compare_exchange(MUT, TAKING, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => {
+                // SAFETY: the pointer is written only in `MOVED` and `GIVING` states,
+                // and no other thread can leave `TAKING` state
+                let pointer = self.pointer.with(|pointer| unsafe { *pointer });
+                self.state.store(MOVED, Ordering::Release);
+                let Some(mut pointer) = pointer else {
+                    return Err(MoveError::BorrowedMutably);
+                };
+                // SAFETY: the pointer was created from the mutable reference of lifetime `'a`,
+                // and this thread is the only one which moved the state out of `MUT`
+                Ok(unsafe { pointer.as_mut() })
+            }
+            Err(REF) => Err(MoveError::BorrowedImmutably),
+            Err(_) => Err(MoveError::BorrowedMutably),
+        }
+    }
+
+    pub(super) fn give_back(&self, unique: &'a mut T) -> Result<(), &'a mut T> {
+        if self
+            .state
+            .compare_exchange(MOVED, GIVING, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(unique);
+        }
+        let pointer = Some(NonNull::from(unique));
+        // SAFETY: no other thread reads the pointer in `GIVING` state,
+        // and the last read of `TAKING` state happens before the write
+        self.pointer.with_mut(|slot| unsafe { *slot = pointer });
+        self.state.store(MUT, Ordering::Release);
+        Ok(())
+    }
+
+    pub(super) fn into_inner(self) -> Option<RefKind<'a, T>> {
+        let state = self.state.load(Ordering::Acquire);
+        // SAFETY: the cell is consumed, so no other thread can access the pointer
+        let mut pointer = self.pointer.with(|pointer| unsafe { *pointer })?;
+        match state {
+            // SAFETY: the pointer was created from the reference of lifetime `'a`
+            // which was not handed out as mutable
+            REF => Some(Ref(unsafe { pointer.as_ref() })),
+            // SAFETY: the pointer was created from the mutable reference of lifetime `'a`
+            // which was not handed out at all
+            MUT => Some(Mut(unsafe { pointer.as_mut() })),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for KindCell<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state.load(Ordering::Acquire) {
+            MUT => "Mut",
+            REF => "Ref",
+            _ => "Moved",
+        };
+        f.debug_struct("KindCell").field("state", &state).finish()
+    }
+}
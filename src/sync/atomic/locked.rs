@@ -0,0 +1,99 @@
+//! Storage of [`AtomicRefKind`](super::AtomicRefKind) which keeps the reference behind a mutex.
+
+use std_crate::sync::PoisonError;
+
+use crate::{Availability, MoveError, MoveResult, Mut, Ref, RefKind};
+
+use super::super::primitives::{AtomicU8, Mutex, Ordering};
+
+const MUT: u8 = 0;
+const REF: u8 = 1;
+const MOVED: u8 = 2;
+
+#[derive(Debug)]
+pub(super) struct KindCell<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    state: AtomicU8,
+    slot: Mutex<Option<RefKind<'a, T>>>,
+}
+
+impl<'a, T> KindCell<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    pub(super) fn new(kind: Option<RefKind<'a, T>>) -> Self {
+        let state = match kind {
+            Some(Ref(_)) => REF,
+            Some(Mut(_)) => MUT,
+            None => MOVED,
+        };
+        Self {
+            state: AtomicU8::new(state),
+            slot: Mutex::new(kind),
+        }
+    }
+
+    pub(super) fn availability(&self) -> Availability {
+        match self.state.load(Ordering::Acquire) {
+            MUT => Availability::Mut,
+            REF => Availability::Ref,
+            _ => Availability::Moved,
+        }
+    }
+
+    pub(super) fn try_move_ref(&self) -> MoveResult<&'a T> {
+        match self
+            .state
+            .compare_exchange(MUT, REF, Ordering::AcqRel, Ordering::Acquire)
+        {
+            // Winner of the state may not have replaced the mutable reference yet,
+            // so whoever locks the slot first does it.
+            Ok(_) | Err(REF) => {
+                let mut slot = self.slot.lock().unwrap_or_else(PoisonError::into_inner);
+                let shared = match slot.take() {
+                    Some(Ref(shared)) => shared,
+                    Some(Mut(unique)) => unique,
+                    None => return Err(MoveError::BorrowedMutably),
+                };
+                *slot = Some(Ref(shared));
+                Ok(shared)
+            }
+            Err(_) => Err(MoveError::BorrowedMutably),
+        }
+    }
+
+    pub(super) fn try_move_mut(&self) -> MoveResult<&'a mut T> {
+        match self
+            .state
+            .compare_exchange(MUT, MOVED, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => {
+                let mut slot = self.slot.lock().unwrap_or_else(PoisonError::into_inner);
+                match slot.take() {
+                    Some(Mut(unique)) => Ok(unique),
+                    _ => Err(MoveError::BorrowedMutably),
+                }
+            }
+            Err(REF) => Err(MoveError::BorrowedImmutably),
+            Err(_) => Err(MoveError::BorrowedMutably),
+        }
+    }
+
+    pub(super) fn give_back(&self, unique: &'a mut T) -> Result<(), &'a mut T> {
+        let mut slot = self.slot.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.state.load(Ordering::Acquire) != MOVED || slot.is_some() {
+            return Err(unique);
+        }
+        *slot = Some(Mut(unique));
+        self.state.store(MUT, Ordering::Release);
+        Ok(())
+    }
+
+    pub(super) fn into_inner(self) -> Option<RefKind<'a, T>> {
+        self.slot
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
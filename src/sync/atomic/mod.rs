@@ -0,0 +1,163 @@
+#[cfg(feature = "lock-free")]
+use self::lock_free::KindCell;
+#[cfg(not(feature = "lock-free"))]
+use self::locked::KindCell;
+use crate::{Availability, MoveMut, MoveRef, MoveResult, Mut, Ref, RefKind};
+
+#[cfg(feature = "lock-free")]
+mod lock_free;
+#[cfg(not(feature = "lock-free"))]
+mod locked;
+
+/// Thread-safe container of [`RefKind`] which allows multiple threads
+/// to race for the reference.
+///
+/// Availability of the reference is tracked by an atomic state word:
+/// the winner of the race is decided by the compare-and-swap of the state,
+/// so the losing threads fail without locking.
+///
+/// By default, the reference itself is stored behind a mutex, which is locked only for a short time
+/// by the threads which won the race (or copy an immutable reference) to take the reference out.
+/// Note that [`try_move_ref`](AtomicRefKind::try_move_ref) can therefore block
+/// while another thread is taking the reference out of the container.
+///
+/// With `lock-free` feature enabled, the reference is stored as a raw pointer
+/// which is guarded by the state word only, so no method of the container blocks.
+/// This storage contains `unsafe` code.
+///
+/// ## Example
+///
+/// ```
+/// use std::thread;
+///
+/// use ref_kind::sync::AtomicRefKind;
+///
+/// let mut numbers = [0, 1, 2, 3];
+/// let atomics: Vec<_> = numbers.iter_mut().map(AtomicRefKind::from).collect();
+///
+/// thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             for atomic in &atomics {
+///                 if let Ok(number) = atomic.try_move_mut() {
+///                     *number += 10;
+///                 }
+///             }
+///         });
+///     }
+/// });
+/// assert_eq!(numbers, [10, 11, 12, 13]);
+/// ```
+#[derive(Debug)]
+pub struct AtomicRefKind<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    cell: KindCell<'a, T>,
+}
+
+impl<'a, T> AtomicRefKind<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    /// Creates new atomic container from the provided kind of reference.
+    pub fn new(kind: RefKind<'a, T>) -> Self {
+        let cell = KindCell::new(Some(kind));
+        Self { cell }
+    }
+
+    /// Creates new atomic container which reference was already moved out.
+    pub fn moved() -> Self {
+        let cell = KindCell::new(None);
+        Self { cell }
+    }
+
+    /// Returns the kind of reference which can be moved out of the container at the moment.
+    ///
+    /// Other threads may move the reference out right after this function returns,
+    /// so the result is only a snapshot of the state.
+    pub fn availability(&self) -> Availability {
+        self.cell.availability()
+    }
+
+    /// Tries to move an immutable reference out of the container.
+    ///
+    /// This function copies an immutable reference or replaces mutable reference with immutable one,
+    /// preserving an immutable reference in the container.
+    pub fn try_move_ref(&self) -> MoveResult<&'a T> {
+        self.cell.try_move_ref()
+    }
+
+    /// Tries to move a mutable reference out of the container.
+    pub fn try_move_mut(&self) -> MoveResult<&'a mut T> {
+        self.cell.try_move_mut()
+    }
+
+    /// Gives the mutable reference back into the container which mutable reference was moved out,
+    /// making it available again.
+    ///
+    /// If the container still holds some reference, the provided reference is returned back as an error.
+    pub fn give_back(&self, unique: &'a mut T) -> Result<(), &'a mut T> {
+        self.cell.give_back(unique)
+    }
+
+    /// Returns the kind of reference which is still stored in the container,
+    /// or [`None`] if mutable reference was already moved out, consuming the `self` value.
+    pub fn into_inner(self) -> Option<RefKind<'a, T>> {
+        self.cell.into_inner()
+    }
+}
+
+/// Convert [`RefKind`] into [`AtomicRefKind`].
+impl<'a, T> From<RefKind<'a, T>> for AtomicRefKind<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    fn from(kind: RefKind<'a, T>) -> Self {
+        Self::new(kind)
+    }
+}
+
+/// Convert immutable reference into [`AtomicRefKind`].
+impl<'a, T> From<&'a T> for AtomicRefKind<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    fn from(shared: &'a T) -> Self {
+        Self::new(Ref(shared))
+    }
+}
+
+/// Convert mutable reference into [`AtomicRefKind`].
+impl<'a, T> From<&'a mut T> for AtomicRefKind<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    fn from(unique: &'a mut T) -> Self {
+        Self::new(Mut(unique))
+    }
+}
+
+/// Immutable reference is moved out of the [`AtomicRefKind`] in the same way as out of the optional [`RefKind`].
+impl<'a, T> MoveRef<'a> for AtomicRefKind<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    type Ref = &'a T;
+
+    fn move_ref(&mut self) -> MoveResult<Self::Ref> {
+        self.try_move_ref()
+    }
+}
+
+/// Mutable reference is moved out of the [`AtomicRefKind`] in the same way as out of the optional [`RefKind`].
+impl<'a, T> MoveMut<'a> for AtomicRefKind<'a, T>
+where
+    T: ?Sized + 'a,
+{
+    type Mut = &'a mut T;
+
+    fn move_mut(&mut self) -> MoveResult<Self::Mut> {
+        self.try_move_mut()
+    }
+}
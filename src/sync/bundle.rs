@@ -0,0 +1,72 @@
+use core::any::{Any, TypeId};
+
+use crate::{MoveResult, RefKind};
+
+use super::SyncTypeMap;
+
+/// Trait for tuples of types which references can be inserted into
+/// and which mutable references can be claimed from [`SyncTypeMap`] all at once.
+///
+/// This trait is implemented for tuples of up to 8 types.
+pub trait Bundle<'a> {
+    /// Tuple of references to the types of the bundle which are inserted into the map.
+    type Kinds: 'a;
+
+    /// Tuple of optional references to the types of the bundle which were stored in the map before insertion.
+    type Previous: 'a;
+
+    /// Inserts references of all the types of the bundle into the map.
+    ///
+    /// See [`SyncTypeMap::insert_bundle`] for details.
+    fn insert(map: &mut SyncTypeMap<'a>, kinds: Self::Kinds) -> Self::Previous;
+
+    /// Tuple of mutable references to the types of the bundle.
+    type Mut: 'a;
+
+    /// Tries to move mutable references of all the types of the bundle out of the map.
+    ///
+    /// See [`SyncTypeMap::try_claim_bundle`] for details.
+    fn try_claim(map: &SyncTypeMap<'a>) -> MoveResult<Option<Self::Mut>>;
+}
+
+macro_rules! impl_bundle {
+    ($($ty:ident $var:ident),+) => {
+        impl<'a, $($ty),+> Bundle<'a> for ($($ty,)+)
+        where
+            $($ty: Any + Send + Sync,)+
+        {
+            type Kinds = ($(RefKind<'a, $ty>,)+);
+
+            type Previous = ($(Option<RefKind<'a, $ty>>,)+);
+
+            fn insert(map: &mut SyncTypeMap<'a>, kinds: Self::Kinds) -> Self::Previous {
+                let ($($var,)+) = kinds;
+                ($(map.insert($var),)+)
+            }
+
+            type Mut = ($(&'a mut $ty,)+);
+
+            fn try_claim(map: &SyncTypeMap<'a>) -> MoveResult<Option<Self::Mut>> {
+                let _gate = map.lock_exclusive();
+                if !map.check_bundle(&mut [$(TypeId::of::<$ty>(),)+])? {
+                    return Ok(None);
+                }
+                // Every type was checked while the map is taken exclusively, so none of the moves fails.
+                let ($($var,)+) = ($(map.take_mut::<$ty>()?,)+);
+                match ($($var,)+) {
+                    ($(Some($var),)+) => Ok(Some(($($var,)+))),
+                    _ => Ok(None),
+                }
+            }
+        }
+    };
+}
+
+impl_bundle!(A a);
+impl_bundle!(A a, B b);
+impl_bundle!(A a, B b, C c);
+impl_bundle!(A a, B b, C c, D d);
+impl_bundle!(A a, B b, C c, D d, E e);
+impl_bundle!(A a, B b, C c, D d, E e, F f);
+impl_bundle!(A a, B b, C c, D d, E e, F f, G g);
+impl_bundle!(A a, B b, C c, D d, E e, F f, G g, H h);
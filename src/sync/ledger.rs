@@ -0,0 +1,143 @@
+use core::fmt::Debug;
+use std_crate::{
+    collections::VecDeque,
+    format,
+    string::String,
+    sync::PoisonError,
+    thread::{self, ThreadId},
+    time::Instant,
+    vec::Vec,
+};
+
+pub use crate::audit::MoveKind;
+
+use crate::{Many, MoveResult};
+
+use super::SyncMany;
+
+/// Record of the move performed on the [`Ledgered`] collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    /// Identifier of the thread which performed the move.
+    pub thread: ThreadId,
+    /// Debug representation of the key used for the move.
+    pub key: String,
+    /// Kind of reference which was requested.
+    pub kind: MoveKind,
+    /// Outcome of the move.
+    pub outcome: MoveResult<()>,
+    /// Moment in time when the move was performed.
+    pub time: Instant,
+}
+
+/// Wrapper of the collection which records every move into a bounded ring buffer.
+///
+/// This is useful to find out which thread claimed the key first
+/// when the collection is shared between threads with [`SyncMany`].
+/// When the buffer is full, the oldest entries are discarded.
+///
+/// ## Example
+///
+/// ```
+/// use std::thread;
+///
+/// use ref_kind::{sync::{Ledgered, MoveKind, SyncMany}, MoveError, RefKind};
+///
+/// let mut numbers = [0, 1, 2];
+/// let many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+/// let many = SyncMany::new(Ledgered::new(many, 16));
+///
+/// thread::scope(|scope| {
+///     scope.spawn(|| many.try_move_mut(1).unwrap());
+/// });
+/// assert_eq!(many.try_move_mut(1), Err(MoveError::BorrowedMutably));
+///
+/// let ledger = many.ledger();
+/// assert_eq!(ledger.len(), 2);
+/// assert_eq!(ledger[0].kind, MoveKind::Mut);
+/// assert_eq!(ledger[0].key, "1");
+/// assert_ne!(ledger[0].thread, thread::current().id());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Ledgered<M> {
+    many: M,
+    entries: VecDeque<LedgerEntry>,
+    capacity: usize,
+}
+
+impl<M> Ledgered<M> {
+    /// Creates new wrapper which records at most `capacity` latest moves.
+    pub fn new(many: M, capacity: usize) -> Self {
+        Self {
+            many,
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns an iterator over recorded moves, from the oldest to the latest.
+    pub fn ledger(&self) -> impl Iterator<Item = &LedgerEntry> {
+        self.entries.iter()
+    }
+
+    /// Clears all the recorded moves.
+    pub fn clear_ledger(&mut self) {
+        self.entries.clear()
+    }
+
+    /// Returns the underlying collection, consuming the `self` value.
+    pub fn into_inner(self) -> M {
+        self.many
+    }
+
+    fn record<R>(&mut self, key: String, kind: MoveKind, outcome: &MoveResult<R>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        let entry = LedgerEntry {
+            thread: thread::current().id(),
+            key,
+            kind,
+            outcome: outcome.as_ref().map(|_| ()).map_err(|error| *error),
+            time: Instant::now(),
+        };
+        self.entries.push_back(entry);
+    }
+}
+
+/// Implementation of [`Many`] trait for [`Ledgered`] which records every move.
+impl<'a, M, Key> Many<'a, Key> for Ledgered<M>
+where
+    M: Many<'a, Key>,
+    Key: Debug,
+{
+    type Ref = M::Ref;
+
+    fn try_move_ref(&mut self, key: Key) -> MoveResult<Self::Ref> {
+        let debug = format!("{key:?}");
+        let outcome = self.many.try_move_ref(key);
+        self.record(debug, MoveKind::Ref, &outcome);
+        outcome
+    }
+
+    type Mut = M::Mut;
+
+    fn try_move_mut(&mut self, key: Key) -> MoveResult<Self::Mut> {
+        let debug = format!("{key:?}");
+        let outcome = self.many.try_move_mut(key);
+        self.record(debug, MoveKind::Mut, &outcome);
+        outcome
+    }
+}
+
+impl<M> SyncMany<Ledgered<M>> {
+    /// Returns a snapshot of the moves recorded by the underlying collection,
+    /// from the oldest to the latest.
+    pub fn ledger(&self) -> Vec<LedgerEntry> {
+        let many = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        many.ledger().cloned().collect()
+    }
+}
@@ -0,0 +1,177 @@
+use std_crate::sync::PoisonError;
+
+use crate::{Many, MoveError, MoveResult};
+
+use super::primitives::Mutex;
+
+/// Thread-safe wrapper of the collection which implements [`Many`] trait.
+///
+/// This wrapper allows to move references out of the collection through a shared reference,
+/// so it can be shared between multiple threads which claim disjoint keys.
+///
+/// If some thread panics while moving reference out of the collection,
+/// the wrapper becomes poisoned and all the subsequent moves return [`MoveError::Poisoned`]
+/// until poisoning is [cleared](SyncMany::clear_poison).
+///
+/// ## Example
+///
+/// ```
+/// use std::thread;
+///
+/// use ref_kind::{sync::SyncMany, RefKind};
+///
+/// let mut numbers = [0, 1, 2, 3];
+/// let many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+/// let many = SyncMany::new(many);
+///
+/// thread::scope(|scope| {
+///     for key in 0..4 {
+///         let many = &many;
+///         scope.spawn(move || {
+///             let number = many.try_move_mut(key).unwrap().unwrap();
+///             *number *= 10;
+///         });
+///     }
+/// });
+/// assert_eq!(numbers, [0, 10, 20, 30]);
+/// ```
+#[derive(Debug, Default)]
+pub struct SyncMany<M> {
+    pub(super) inner: Mutex<M>,
+}
+
+impl<M> SyncMany<M> {
+    /// Creates new thread-safe wrapper of the provided collection.
+    pub fn new(many: M) -> Self {
+        let inner = Mutex::new(many);
+        Self { inner }
+    }
+
+    /// Tries to move an immutable reference out of the collection.
+    ///
+    /// See [`Many::try_move_ref`] for details.
+    pub fn try_move_ref<'a, Key>(&self, key: Key) -> MoveResult<M::Ref>
+    where
+        M: Many<'a, Key>,
+    {
+        let mut many = self.inner.lock().map_err(|_| MoveError::Poisoned)?;
+        many.try_move_ref(key)
+    }
+
+    /// Moves an immutable reference out of the collection.
+    ///
+    /// See [`Many::move_ref`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the collection
+    /// or the collection was poisoned.
+    #[track_caller]
+    pub fn move_ref<'a, Key>(&self, key: Key) -> M::Ref
+    where
+        M: Many<'a, Key>,
+    {
+        match self.try_move_ref(key) {
+            Ok(result) => result,
+            Err(error) => crate::many::move_panic(error),
+        }
+    }
+
+    /// Tries to move a mutable reference out of the collection.
+    ///
+    /// See [`Many::try_move_mut`] for details.
+    pub fn try_move_mut<'a, Key>(&self, key: Key) -> MoveResult<M::Mut>
+    where
+        M: Many<'a, Key>,
+    {
+        let mut many = self.inner.lock().map_err(|_| MoveError::Poisoned)?;
+        many.try_move_mut(key)
+    }
+
+    /// Moves a mutable reference out of the collection.
+    ///
+    /// See [`Many::move_mut`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the collection,
+    /// the value was already borrowed as immutable or the collection was poisoned.
+    #[track_caller]
+    pub fn move_mut<'a, Key>(&self, key: Key) -> M::Mut
+    where
+        M: Many<'a, Key>,
+    {
+        match self.try_move_mut(key) {
+            Ok(result) => result,
+            Err(error) => crate::many::move_panic(error),
+        }
+    }
+
+    /// Locks the collection and passes it into the closure,
+    /// so that several references can be moved out at once.
+    ///
+    /// If the closure panics while the collection is locked,
+    /// the wrapper becomes poisoned and all the subsequent moves return [`MoveError::Poisoned`]
+    /// until poisoning is [cleared](SyncMany::clear_poison).
+    /// This way code which catches the panic cannot observe half-claimed collection by accident.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MoveError::Poisoned`] if the collection was poisoned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{sync::SyncMany, Many, RefKind};
+    ///
+    /// let mut numbers = [0, 1, 2, 3];
+    /// let many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    /// let many = SyncMany::new(many);
+    ///
+    /// let (first, last) = many
+    ///     .scope(|many| (many.try_move_mut(0), many.try_move_mut(3)))
+    ///     .unwrap();
+    /// *first.unwrap().unwrap() += 10;
+    /// *last.unwrap().unwrap() += 10;
+    /// assert_eq!(numbers, [10, 1, 2, 13]);
+    /// ```
+    pub fn scope<F, R>(&self, f: F) -> MoveResult<R>
+    where
+        F: FnOnce(&mut M) -> R,
+    {
+        let mut many = self.inner.lock().map_err(|_| MoveError::Poisoned)?;
+        Ok(f(&mut many))
+    }
+
+    /// Checks if the collection is poisoned.
+    #[cfg(not(loom))]
+    pub fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Clears the poisoned state of the collection.
+    #[cfg(not(loom))]
+    pub fn clear_poison(&self) {
+        self.inner.clear_poison()
+    }
+
+    /// Returns a mutable reference to the underlying collection.
+    ///
+    /// No locking is needed because this call borrows the wrapper mutably.
+    pub fn get_mut(&mut self) -> &mut M {
+        self.inner.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the underlying collection, consuming the `self` value.
+    pub fn into_inner(self) -> M {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<M> From<M> for SyncMany<M> {
+    fn from(many: M) -> Self {
+        Self::new(many)
+    }
+}
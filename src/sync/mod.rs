@@ -0,0 +1,24 @@
+//! Provides thread-safe containers which allow to move references out of them
+//! from multiple threads at once.
+
+#[cfg(feature = "debug-ledger")]
+#[cfg_attr(docsrs, doc(cfg(feature = "debug-ledger")))]
+pub use self::ledger::{LedgerEntry, Ledgered, MoveKind};
+pub use self::{
+    atomic::AtomicRefKind,
+    bundle::Bundle,
+    many::SyncMany,
+    parallel::run_parallel,
+    scatter::{scatter, ScatterError},
+    type_map::SyncTypeMap,
+};
+
+mod atomic;
+mod bundle;
+#[cfg(feature = "debug-ledger")]
+mod ledger;
+mod many;
+mod parallel;
+mod primitives;
+mod scatter;
+mod type_map;
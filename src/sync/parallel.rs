@@ -0,0 +1,93 @@
+use std_crate::vec::Vec;
+
+use crate::{
+    access::{AccessSet, Claim, ClaimError},
+    Many, ManyAvailability, MoveError,
+};
+
+use super::scatter::spawn_each;
+
+/// Claims references declared by each of the access sets
+/// and passes each claim into its own scoped thread.
+///
+/// Conflicts between the access sets and availability of their keys are checked up front:
+/// if some of them [conflict](AccessSet::conflicts_with) with each other or with themselves,
+/// or some key is missing from the collection or was already claimed elsewhere,
+/// no reference is moved out of the collection and an error with the conflicting key is returned.
+/// Use [`schedule`](crate::access::schedule) to split systems into batches without conflicts.
+///
+/// Closure receives index of the access set and references claimed by it.
+/// Results of the closure are returned in the order of the access sets.
+///
+/// # Panics
+///
+/// If some of the threads panics, the panic is propagated to the caller
+/// after all the threads are joined.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{
+///     access::{schedule, AccessSet},
+///     sync::run_parallel,
+///     RefKind,
+/// };
+///
+/// let mut numbers = [1, 2, 3, 4];
+/// let systems = [
+///     AccessSet::new().read(0).write(1),
+///     AccessSet::new().read(1).write(0),
+///     AccessSet::new().read(2).write(3),
+/// ];
+///
+/// for batch in schedule(&systems) {
+///     let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+///     let accesses = batch.iter().map(|&index| &systems[index]);
+///     run_parallel(&mut many, accesses, |_, claim| {
+///         let read: i32 = claim.reads.into_iter().flatten().sum();
+///         for write in claim.writes.into_iter().flatten() {
+///             *write += read;
+///         }
+///     })
+///     .unwrap();
+/// }
+/// assert_eq!(numbers, [4, 3, 3, 7]);
+/// ```
+pub fn run_parallel<'a, 's, M, Key, I, F, R>(
+    many: &mut M,
+    accesses: I,
+    f: F,
+) -> Result<Vec<R>, ClaimError<Key>>
+where
+    M: ?Sized + Many<'a, Key> + ManyAvailability<Key>,
+    M::Ref: Send,
+    M::Mut: Send,
+    Key: PartialEq + Clone + 's,
+    I: IntoIterator<Item = &'s AccessSet<Key>>,
+    F: Fn(usize, Claim<M::Ref, M::Mut>) -> R + Sync,
+    R: Send,
+{
+    let accesses: Vec<_> = accesses.into_iter().collect();
+    for (index, access) in accesses.iter().enumerate() {
+        if let Some(key) = access.self_conflict() {
+            return Err(ClaimError::new(key, MoveError::BorrowedMutably));
+        }
+        for previous in &accesses[..index] {
+            if let Some(key) = previous.conflicts_with(access) {
+                let error = match previous.writes().contains(key) {
+                    true => MoveError::BorrowedMutably,
+                    false => MoveError::BorrowedImmutably,
+                };
+                return Err(ClaimError::new(key, error));
+            }
+        }
+        access.check(many)?;
+    }
+
+    let claims = accesses
+        .into_iter()
+        .map(|access| access.claim(many))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(spawn_each(claims, &f))
+}
@@ -0,0 +1,36 @@
+//! Synchronization primitives of the thread-safe containers,
+//! which are replaced with the ones of `loom` crate when building with `--cfg loom`.
+
+#[cfg(not(loom))]
+pub(crate) use core::sync::atomic::{AtomicU8, Ordering};
+#[cfg(loom)]
+pub(crate) use loom::sync::{
+    atomic::{AtomicU8, Ordering},
+    Mutex, RwLock, RwLockWriteGuard,
+};
+#[cfg(not(loom))]
+pub(crate) use std_crate::sync::{Mutex, RwLock, RwLockWriteGuard};
+
+#[cfg(all(loom, feature = "lock-free"))]
+pub(crate) use loom::cell::UnsafeCell;
+
+/// Cell with the same interface as the one of `loom` crate,
+/// so accesses of the lock-free storage can be checked by `loom`.
+#[cfg(all(not(loom), feature = "lock-free"))]
+#[derive(Debug)]
+pub(crate) struct UnsafeCell<T>(core::cell::UnsafeCell<T>);
+
+#[cfg(all(not(loom), feature = "lock-free"))]
+impl<T> UnsafeCell<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(core::cell::UnsafeCell::new(value))
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+        f(self.0.get())
+    }
+
+    pub(crate) fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
@@ -0,0 +1,140 @@
+use core::fmt;
+use std_crate::{panic, thread, vec::Vec};
+
+use crate::{
+    access::{check_write, ClaimError},
+    Many, ManyAvailability,
+};
+
+/// Moves mutable references out of the collection by groups of keys
+/// and passes each group into its own scoped thread.
+///
+/// Disjointness of the groups and [availability](ManyAvailability) of every key are checked up front:
+/// if the same key occurs more than once, [`ScatterError::DuplicateKey`] is returned,
+/// and if some key is missing from the collection or was already moved out,
+/// [`ScatterError::Unavailable`] is returned.
+/// In both cases no reference is moved out of the collection.
+///
+/// Closure receives index of the group and mutable references moved out by the keys of the group.
+/// Results of the closure are returned in the order of the groups.
+///
+/// # Panics
+///
+/// If some of the threads panics, the panic is propagated to the caller
+/// after all the threads are joined.
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{sync::scatter, RefKind};
+///
+/// let mut numbers = [1, 2, 3, 4, 5];
+/// let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+///
+/// let sums = scatter(&mut many, [vec![0, 2], vec![1, 3, 4]], |_, group| {
+///     group.into_iter().flatten().map(|number| {
+///         *number *= 2;
+///         *number
+///     }).sum::<i32>()
+/// }).unwrap();
+///
+/// assert_eq!(sums, [8, 22]);
+/// assert_eq!(numbers, [2, 4, 6, 8, 10]);
+/// ```
+pub fn scatter<'a, M, G, F, R>(many: &mut M, groups: G, f: F) -> Result<Vec<R>, ScatterError>
+where
+    M: ?Sized + Many<'a, usize> + ManyAvailability<usize>,
+    M::Mut: Send,
+    G: IntoIterator,
+    G::Item: IntoIterator<Item = usize>,
+    F: Fn(usize, Vec<M::Mut>) -> R + Sync,
+    R: Send,
+{
+    let groups: Vec<Vec<_>> = groups
+        .into_iter()
+        .map(|group| group.into_iter().collect())
+        .collect();
+
+    let mut keys: Vec<_> = groups.iter().flatten().copied().collect();
+    keys.sort_unstable();
+    if let Some(pair) = keys.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(ScatterError::DuplicateKey(pair[0]));
+    }
+    for key in &keys {
+        check_write(many, key).map_err(|error| ClaimError::new(key, error))?;
+    }
+
+    let claims = groups
+        .into_iter()
+        .map(|group| {
+            group
+                .into_iter()
+                .map(|key| {
+                    many.try_move_mut(key)
+                        .map_err(|error| ClaimError { key, error })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(spawn_each(claims, &f))
+}
+
+/// Error which can occur when scattering references of the collection by [`scatter`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ScatterError {
+    /// The key occurs in the groups more than once.
+    DuplicateKey(usize),
+    /// Mutable reference cannot be moved out of the collection by the key.
+    Unavailable(ClaimError<usize>),
+}
+
+impl From<ClaimError<usize>> for ScatterError {
+    fn from(error: ClaimError<usize>) -> Self {
+        Self::Unavailable(error)
+    }
+}
+
+impl fmt::Display for ScatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateKey(key) => write!(f, "key {key} occurs in the groups more than once"),
+            Self::Unavailable(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std_crate::error::Error for ScatterError {
+    fn source(&self) -> Option<&(dyn std_crate::error::Error + 'static)> {
+        match self {
+            Self::DuplicateKey(_) => None,
+            Self::Unavailable(error) => Some(error),
+        }
+    }
+}
+
+/// Passes each claim with its index into its own scoped thread, returning results in the order of claims.
+///
+/// If some of the threads panics, the panic is propagated after all the threads are joined.
+pub(super) fn spawn_each<C, F, R>(claims: Vec<C>, f: &F) -> Vec<R>
+where
+    C: Send,
+    F: Fn(usize, C) -> R + Sync,
+    R: Send,
+{
+    thread::scope(|scope| {
+        let handles: Vec<_> = claims
+            .into_iter()
+            .enumerate()
+            .map(|(index, claim)| scope.spawn(move || f(index, claim)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|payload| panic::resume_unwind(payload))
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,278 @@
+use core::any::{Any, TypeId};
+use std_crate::{collections::HashMap, sync::PoisonError};
+
+use crate::{Availability, MoveError, MoveResult, Mut, Ref, RefKind};
+
+use super::{
+    primitives::{RwLock, RwLockWriteGuard},
+    AtomicRefKind, Bundle,
+};
+
+type AnyRefKind<'a> = AtomicRefKind<'a, dyn Any + Send + Sync>;
+
+/// Thread-safe heterogeneous map which holds at most one reference for each type.
+///
+/// Each type has its own [atomic](AtomicRefKind) slot, so references of different types
+/// can be claimed from different threads at the same time without contending for one lock,
+/// while conflicting claims of the same type surface as errors.
+/// Only [bundle claims](SyncTypeMap::try_claim_bundle) take the map exclusively for a short time,
+/// so other threads never observe the bundle claimed partially.
+///
+/// ## Example
+///
+/// ```
+/// use std::thread;
+///
+/// use ref_kind::{sync::SyncTypeMap, MoveError, RefKind};
+///
+/// struct Physics(u32);
+/// struct Audio(u32);
+///
+/// let mut physics = Physics(0);
+/// let mut audio = Audio(0);
+///
+/// let mut resources = SyncTypeMap::new();
+/// resources.insert(RefKind::from(&mut physics));
+/// resources.insert(RefKind::from(&mut audio));
+///
+/// thread::scope(|scope| {
+///     scope.spawn(|| resources.try_move_mut::<Physics>().unwrap().unwrap().0 += 1);
+///     scope.spawn(|| resources.try_move_mut::<Audio>().unwrap().unwrap().0 += 2);
+/// });
+/// let error = resources.try_move_mut::<Physics>();
+/// assert_eq!(error.err(), Some(MoveError::BorrowedMutably));
+///
+/// assert_eq!((physics.0, audio.0), (1, 2));
+/// ```
+#[derive(Debug, Default)]
+pub struct SyncTypeMap<'a> {
+    slots: HashMap<TypeId, AnyRefKind<'a>>,
+    /// Shared by moves of single types and taken exclusively by bundle claims.
+    gate: RwLock<()>,
+}
+
+impl<'a> SyncTypeMap<'a> {
+    /// Creates new empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the reference of the type into the map,
+    /// returning the reference of this type which was stored before, if any.
+    pub fn insert<T>(&mut self, kind: RefKind<'a, T>) -> Option<RefKind<'a, T>>
+    where
+        T: Any + Send + Sync,
+    {
+        let kind: RefKind<'a, dyn Any + Send + Sync> = match kind {
+            Ref(shared) => Ref(shared),
+            Mut(unique) => Mut(unique),
+        };
+        let previous = self.slots.insert(TypeId::of::<T>(), kind.into())?;
+        match previous.into_inner()? {
+            Ref(shared) => shared.downcast_ref().map(Ref),
+            Mut(unique) => unique.downcast_mut().map(Mut),
+        }
+    }
+
+    /// Inserts references of all the types of the bundle into the map in a single call,
+    /// returning the references of these types which were stored before, if any.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{sync::SyncTypeMap, RefKind};
+    ///
+    /// let (mut position, velocity) = (1, 2.0);
+    ///
+    /// let mut map = SyncTypeMap::new();
+    /// let previous = map.insert_bundle::<(i32, f64)>((
+    ///     RefKind::from(&mut position),
+    ///     RefKind::from(&velocity),
+    /// ));
+    /// assert!(matches!(previous, (None, None)));
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn insert_bundle<B>(&mut self, kinds: B::Kinds) -> B::Previous
+    where
+        B: Bundle<'a>,
+    {
+        B::insert(self, kinds)
+    }
+
+    /// Checks if the map contains the reference of the type,
+    /// even if it was already moved out.
+    pub fn contains<T>(&self) -> bool
+    where
+        T: Any,
+    {
+        self.slots.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Returns the count of types stored in the map.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Checks if the map contains no types.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Tries to move an immutable reference of the type out of the map.
+    ///
+    /// Returns [`None`] if the map contains no reference of this type.
+    pub fn try_move_ref<T>(&self) -> MoveResult<Option<&'a T>>
+    where
+        T: Any,
+    {
+        let _gate = self.gate.read().unwrap_or_else(PoisonError::into_inner);
+        let slot = match self.slots.get(&TypeId::of::<T>()) {
+            Some(slot) => slot,
+            None => return Ok(None),
+        };
+        let shared = slot.try_move_ref()?;
+        Ok(shared.downcast_ref())
+    }
+
+    /// Moves an immutable reference of the type out of the map.
+    ///
+    /// Returns [`None`] if the map contains no reference of this type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the map.
+    #[track_caller]
+    pub fn move_ref<T>(&self) -> Option<&'a T>
+    where
+        T: Any,
+    {
+        match self.try_move_ref() {
+            Ok(result) => result,
+            Err(error) => crate::many::move_panic(error),
+        }
+    }
+
+    /// Tries to move a mutable reference of the type out of the map.
+    ///
+    /// Returns [`None`] if the map contains no reference of this type.
+    pub fn try_move_mut<T>(&self) -> MoveResult<Option<&'a mut T>>
+    where
+        T: Any,
+    {
+        let _gate = self.gate.read().unwrap_or_else(PoisonError::into_inner);
+        self.take_mut()
+    }
+
+    /// Moves a mutable reference of the type out of the map.
+    ///
+    /// Returns [`None`] if the map contains no reference of this type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference was already moved out of the map
+    /// or the value was already borrowed as immutable.
+    #[track_caller]
+    pub fn move_mut<T>(&self) -> Option<&'a mut T>
+    where
+        T: Any,
+    {
+        match self.try_move_mut() {
+            Ok(result) => result,
+            Err(error) => crate::many::move_panic(error),
+        }
+    }
+
+    /// Tries to move mutable references of all the types of the bundle out of the map at once.
+    ///
+    /// Either all the references are moved out or none of them:
+    /// availability of every type of the bundle is checked before any reference is moved out,
+    /// while the map is taken exclusively, so no other thread observes the bundle claimed partially.
+    /// The same type cannot be claimed twice, so such bundle results in [`MoveError::BorrowedMutably`] error.
+    ///
+    /// Returns [`None`] if the map contains no reference of some type of the bundle.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{sync::SyncTypeMap, MoveError, RefKind};
+    ///
+    /// let (mut position, mut velocity, mut mass) = (1, 2.0, 3_u8);
+    ///
+    /// let mut map = SyncTypeMap::new();
+    /// map.insert(RefKind::from(&mut position));
+    /// map.insert(RefKind::from(&mut velocity));
+    /// map.insert(RefKind::from(&mass));
+    ///
+    /// let error = map.try_claim_bundle::<(i32, f64, u8)>();
+    /// assert_eq!(error.err(), Some(MoveError::BorrowedImmutably));
+    ///
+    /// let (position, velocity) = map.try_claim_bundle::<(i32, f64)>().unwrap().unwrap();
+    /// *position += 1;
+    /// *velocity *= 2.0;
+    /// ```
+    ///
+    /// [`MoveError::BorrowedMutably`]: crate::MoveError::BorrowedMutably
+    pub fn try_claim_bundle<B>(&self) -> MoveResult<Option<B::Mut>>
+    where
+        B: Bundle<'a>,
+    {
+        B::try_claim(self)
+    }
+
+    /// Moves mutable references of all the types of the bundle out of the map at once.
+    ///
+    /// See [`try_claim_bundle`](SyncTypeMap::try_claim_bundle) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if mutable reference of some type of the bundle was already moved out of the map
+    /// or the value was already borrowed as immutable.
+    #[track_caller]
+    pub fn claim_bundle<B>(&self) -> Option<B::Mut>
+    where
+        B: Bundle<'a>,
+    {
+        match self.try_claim_bundle::<B>() {
+            Ok(result) => result,
+            Err(error) => crate::many::move_panic(error),
+        }
+    }
+
+    /// Takes the map exclusively, so no reference can be moved out by other threads until the guard is dropped.
+    pub(super) fn lock_exclusive(&self) -> RwLockWriteGuard<'_, ()> {
+        self.gate.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Checks that mutable references of all the types can be moved out of the map.
+    ///
+    /// Returns `false` if the map contains no reference of some type.
+    pub(super) fn check_bundle(&self, types: &mut [TypeId]) -> MoveResult<bool> {
+        types.sort_unstable();
+        if types.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(MoveError::BorrowedMutably);
+        }
+        let mut found = true;
+        for type_id in types.iter() {
+            match self.slots.get(type_id).map(AtomicRefKind::availability) {
+                Some(Availability::Mut) => {}
+                Some(Availability::Ref) => return Err(MoveError::BorrowedImmutably),
+                Some(Availability::Moved) => return Err(MoveError::BorrowedMutably),
+                None => found = false,
+            }
+        }
+        Ok(found)
+    }
+
+    /// Moves a mutable reference of the type out of the map without taking the gate.
+    pub(super) fn take_mut<T>(&self) -> MoveResult<Option<&'a mut T>>
+    where
+        T: Any,
+    {
+        let slot = match self.slots.get(&TypeId::of::<T>()) {
+            Some(slot) => slot,
+            None => return Ok(None),
+        };
+        let unique = slot.try_move_mut()?;
+        Ok(unique.downcast_mut())
+    }
+}
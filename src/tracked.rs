@@ -0,0 +1,110 @@
+use alloc_crate::vec::Vec;
+
+use crate::{Many, MoveResult};
+
+/// Wrapper of the collection which tracks keys of mutable references moved out of it.
+///
+/// Each key which mutable reference was successfully moved out is recorded,
+/// so systems can find out which values could have been changed
+/// since the collection was built or since the last call of [`reset_tracking`](Tracked::reset_tracking).
+///
+/// ## Example
+///
+/// ```
+/// use ref_kind::{Many, RefKind, Tracked};
+///
+/// let mut numbers = [0, 1, 2, 3];
+/// let many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+/// let mut many = Tracked::new(many);
+///
+/// let _ = many.try_move_mut(2);
+/// let _ = many.try_move_ref(1);
+/// let _ = many.try_move_mut(2);
+/// assert_eq!(many.changed_keys(), [2]);
+///
+/// many.reset_tracking();
+/// assert!(many.changed_keys().is_empty());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug, Clone, Default)]
+pub struct Tracked<M, Key> {
+    many: M,
+    changed: Vec<Key>,
+}
+
+impl<M, Key> Tracked<M, Key> {
+    /// Creates new wrapper of the collection with no tracked keys.
+    pub fn new(many: M) -> Self {
+        Self {
+            many,
+            changed: Vec::new(),
+        }
+    }
+
+    /// Returns keys which mutable references were moved out, in the order of moves.
+    pub fn changed_keys(&self) -> &[Key] {
+        &self.changed
+    }
+
+    /// Returns keys which mutable references were moved out, in the ascending order of keys.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ref_kind::{Many, RefKind, Tracked};
+    ///
+    /// let mut numbers = [0, 1, 2, 3];
+    /// let many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    /// let mut many = Tracked::new(many);
+    ///
+    /// let _ = many.try_move_mut(3);
+    /// let _ = many.try_move_mut(0);
+    /// assert_eq!(many.changed_keys(), [3, 0]);
+    /// assert_eq!(many.sorted_changed_keys(), [0, 3]);
+    /// ```
+    pub fn sorted_changed_keys(&self) -> Vec<Key>
+    where
+        Key: Ord + Clone,
+    {
+        let mut keys = self.changed.clone();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Forgets all the tracked keys, returning them in the order of moves.
+    pub fn reset_tracking(&mut self) -> Vec<Key> {
+        core::mem::take(&mut self.changed)
+    }
+
+    /// Returns an immutable reference to the underlying collection.
+    pub fn get_ref(&self) -> &M {
+        &self.many
+    }
+
+    /// Returns the underlying collection, consuming the `self` value.
+    pub fn into_inner(self) -> M {
+        self.many
+    }
+}
+
+/// Implementation of [`Many`] trait for [`Tracked`] which tracks keys of moved mutable references.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<'a, M, Key> Many<'a, Key> for Tracked<M, Key>
+where
+    M: Many<'a, Key>,
+    Key: Clone,
+{
+    type Ref = M::Ref;
+
+    fn try_move_ref(&mut self, key: Key) -> MoveResult<Self::Ref> {
+        self.many.try_move_ref(key)
+    }
+
+    type Mut = M::Mut;
+
+    fn try_move_mut(&mut self, key: Key) -> MoveResult<Self::Mut> {
+        let unique = self.many.try_move_mut(key.clone())?;
+        self.changed.push(key);
+        Ok(unique)
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "alloc")]
+
+use std::collections::BTreeMap;
+
+use ref_kind::{access::AccessSet, Many, MoveError, RefKind};
+
+#[test]
+fn claim_is_all_or_nothing() {
+    let mut numbers = [0, 1, 2, 3];
+    let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    let _ = many.try_move_mut(3);
+
+    let plan = AccessSet::new().read(0).write(1).write(2).write(3);
+    let error = plan.claim(&mut many).unwrap_err();
+    assert_eq!((error.key, error.error), (3, MoveError::BorrowedMutably));
+
+    // Nothing was moved out by the failed claim
+    assert_eq!(many.try_move_mut(0), Ok(Some(&mut 0)));
+    assert_eq!(many.try_move_mut(1), Ok(Some(&mut 1)));
+    assert_eq!(many.try_move_mut(2), Ok(Some(&mut 2)));
+}
+
+#[test]
+fn claim_checks_reads_before_moving_writes() {
+    let (mut first, mut second) = (1, 2);
+    let mut many = BTreeMap::from([
+        ("first", Some(RefKind::from(&mut first))),
+        ("second", Some(RefKind::from(&mut second))),
+    ]);
+    let _ = many.try_move_mut("second");
+
+    let plan = AccessSet::new().write("first").read("second");
+    let error = plan.claim(&mut many).unwrap_err();
+    assert_eq!(
+        (error.key, error.error),
+        ("second", MoveError::BorrowedMutably)
+    );
+    assert_eq!(many.try_move_mut("first"), Ok(Some(&mut 1)));
+}
+
+#[test]
+fn claim_reports_missing_key() {
+    let mut number = 0;
+    let mut many = BTreeMap::from([(0, Some(RefKind::from(&mut number)))]);
+
+    let plan = AccessSet::new().write(0).read(1);
+    let error = plan.claim(&mut many).unwrap_err();
+    assert_eq!((error.key, error.error), (1, MoveError::NotFound));
+    assert_eq!(many.try_move_mut(0), Ok(Some(&mut 0)));
+}
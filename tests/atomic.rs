@@ -0,0 +1,31 @@
+#![cfg(feature = "atomic")]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use ref_kind::Many;
+
+#[test]
+fn counters_are_not_claimed() {
+    let counters = [AtomicUsize::new(0), AtomicUsize::new(10)];
+    let mut many = counters.each_ref().map(Some);
+
+    let first = many.try_move_mut(0).unwrap().unwrap();
+    let first_again = many.try_move_mut(0).unwrap().unwrap();
+    first.fetch_add(1, Ordering::Relaxed);
+    first_again.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(
+        many.try_move_ref(0)
+            .unwrap()
+            .unwrap()
+            .load(Ordering::Relaxed),
+        2
+    );
+
+    let mut flags = [None, Some(&AtomicBool::new(true))];
+    assert!(flags.try_move_mut(0).is_err());
+    assert!(flags
+        .try_move_mut(1)
+        .unwrap()
+        .unwrap()
+        .swap(false, Ordering::Relaxed));
+}
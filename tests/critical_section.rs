@@ -0,0 +1,33 @@
+#![cfg(feature = "critical-section")]
+
+use std::thread;
+
+use ref_kind::{CriticalMany, MoveError, RefKind};
+
+static NUMBERS: CriticalMany<[Option<RefKind<'static, u32>>; 2]> = CriticalMany::new([None, None]);
+
+#[test]
+fn static_shared_with_handler() {
+    let numbers: &'static mut [u32; 2] = Box::leak(Box::new([1, 2]));
+    let previous = NUMBERS.replace(numbers.each_mut().map(|i| Some(RefKind::from(i))));
+    assert!(previous.iter().all(Option::is_none));
+
+    // Thread stands for the interrupt handler which claims its own number
+    thread::spawn(|| *NUMBERS.move_mut(1).unwrap() += 10)
+        .join()
+        .unwrap();
+
+    let first = NUMBERS.move_ref(0).unwrap();
+    assert_eq!(*first, 1);
+    assert_eq!(NUMBERS.try_move_mut(0), Err(MoveError::BorrowedImmutably));
+    assert_eq!(NUMBERS.try_move_ref(1), Err(MoveError::BorrowedMutably));
+}
+
+#[test]
+fn try_replace() {
+    let mut number = 1;
+    let many = CriticalMany::new([None]);
+    let previous = many.try_replace([Some(RefKind::from(&mut number))]);
+    assert!(previous.is_ok_and(|previous| previous[0].is_none()));
+    assert_eq!(many.try_move_ref(0), Ok(Some(&1)));
+}
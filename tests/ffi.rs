@@ -0,0 +1,67 @@
+#![cfg(feature = "ffi")]
+#![allow(unsafe_code)]
+
+use core::{ffi::c_void, ptr};
+
+use ref_kind::{
+    ffi::{self, RefKindMap, RefKindStatus},
+    MoveError,
+};
+
+#[test]
+fn registry_tracks_availability() {
+    let mut first = 1;
+    let second = 2;
+    let first_ptr = (&mut first as *mut i32).cast::<c_void>();
+    let second_ptr = (&second as *const i32).cast::<c_void>();
+
+    let mut map = RefKindMap::new();
+    map.insert_mut(b"first", first_ptr);
+    map.insert_ref(b"second", second_ptr);
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(
+        map.try_move_mut(b"second"),
+        Err(MoveError::BorrowedImmutably)
+    );
+    assert_eq!(map.try_move_ref(b"second"), Ok(Some(second_ptr)));
+    assert_eq!(map.try_move_mut(b"first"), Ok(Some(first_ptr)));
+    assert_eq!(map.try_move_ref(b"first"), Err(MoveError::BorrowedMutably));
+    assert_eq!(map.try_move_mut(b"third"), Ok(None));
+}
+
+#[test]
+fn extern_functions_report_status() {
+    let value = 42;
+    let pointer = (&value as *const i32).cast::<c_void>();
+    let key = b"value";
+    let mut shared = ptr::null();
+    let mut unique = ptr::null_mut();
+
+    let map = ffi::ref_kind_map_new();
+    // SAFETY: handle is not destroyed until the end, keys and outputs are valid
+    unsafe {
+        let status = ffi::ref_kind_map_insert_ref(map, key.as_ptr(), key.len(), pointer);
+        assert_eq!(status, RefKindStatus::Ok);
+        let status = ffi::ref_kind_map_insert_ref(map, key.as_ptr(), key.len(), ptr::null());
+        assert_eq!(status, RefKindStatus::NullPointer);
+
+        let status = ffi::ref_kind_map_move_mut(map, key.as_ptr(), key.len(), &mut unique);
+        assert_eq!(status, RefKindStatus::BorrowedImmutably);
+        assert!(unique.is_null());
+        let status = ffi::ref_kind_map_move_ref(map, key.as_ptr(), key.len(), &mut shared);
+        assert_eq!(status, RefKindStatus::Ok);
+        assert_eq!(shared, pointer);
+
+        let status = ffi::ref_kind_map_move_ref(map, ptr::null(), 0, &mut shared);
+        assert_eq!(status, RefKindStatus::NotFound);
+        let status = ffi::ref_kind_map_move_ref(map, ptr::null(), 1, &mut shared);
+        assert_eq!(status, RefKindStatus::NullPointer);
+        let status =
+            ffi::ref_kind_map_move_ref(ptr::null_mut(), key.as_ptr(), key.len(), &mut shared);
+        assert_eq!(status, RefKindStatus::NullPointer);
+
+        ffi::ref_kind_map_free(map);
+        ffi::ref_kind_map_free(ptr::null_mut());
+    }
+}
@@ -0,0 +1,12 @@
+#![cfg(feature = "debug-graph")]
+
+use ref_kind::{graph::BorrowGraph, Availability};
+
+#[test]
+fn dot_escapes_keys() {
+    let mut graph = BorrowGraph::new();
+    graph.push("a\"b", Availability::Ref);
+
+    let expected = "digraph borrows {\n    n0 [label=\"\\\"a\\\\\\\"b\\\": ref\"];\n}";
+    assert_eq!(graph.to_dot(), expected);
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "indexmap")]
+
+use ref_kind::{Many, MoveError, RefKind, RefKindIndexMap};
+
+#[test]
+fn shift_remove_keeps_order() {
+    let mut numbers = [0, 1, 2, 3];
+    let mut map: RefKindIndexMap<_, _> = numbers
+        .iter_mut()
+        .enumerate()
+        .map(|(i, n)| (i, RefKind::from(n)))
+        .collect();
+
+    assert!(map.shift_remove(&1).is_some());
+    assert_eq!(map.get_index_of(&3), Some(2));
+    assert_eq!(map.try_move_mut_at(1), Ok(Some(&mut 2)));
+    assert_eq!(map.try_move_ref(2), Err(MoveError::BorrowedMutably));
+    assert_eq!(map.try_move_ref_at(2), Ok(Some(&3)));
+    assert_eq!(map.try_move_mut(3), Err(MoveError::BorrowedImmutably));
+    assert_eq!(map.try_move_mut_at(3), Ok(None));
+}
+
+#[test]
+fn allocated_bytes_grows_with_capacity() {
+    let number = 42;
+    let mut map = RefKindIndexMap::<&str, u64>::new();
+    assert_eq!(map.allocated_bytes(), 0);
+
+    map.insert("number", Some(RefKind::from(&number)));
+    assert!(map.allocated_bytes() >= size_of::<&str>() + size_of::<Option<RefKind<u64>>>());
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "intern")]
+
+use ref_kind::{intern::SymbolTable, Many, MoveError, RefKind};
+
+#[test]
+fn dense_by_symbol() {
+    let mut symbols = SymbolTable::new();
+    let names = ["gravity", "friction", "gravity"].map(|name| symbols.intern(name));
+    assert_eq!(names[0], names[2]);
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols.resolve(names[1]), Some("friction"));
+
+    let mut values = [9.8, 0.3];
+    let mut many: Vec<_> = values.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+
+    let friction = symbols.try_move_mut(&mut many, "friction").unwrap();
+    assert_eq!(friction, Some(Some(&mut 0.3)));
+    let error = many.try_move_ref(names[1]);
+    assert_eq!(error, Err(MoveError::BorrowedMutably));
+    assert_eq!(many.try_move_ref(names[0]), Ok(Some(&9.8)));
+}
+
+#[test]
+fn try_intern_shares_symbols() {
+    let mut symbols = SymbolTable::new();
+    let gravity = symbols.try_intern("gravity").unwrap();
+    assert_eq!(symbols.try_intern("gravity"), Ok(gravity));
+    assert_eq!(symbols.intern("gravity"), gravity);
+    assert_eq!(symbols.len(), 1);
+}
@@ -17,3 +17,82 @@ fn from_mut() {
     assert!(number_mut.is_mut());
     assert_eq!(RefKind::Mut(&mut 42), number_mut);
 }
+
+#[test]
+fn const_table() {
+    const NUMBER: RefKind<'static, i32> = RefKind::Ref(&42);
+    const TABLE: [Option<RefKind<'static, i32>>; 2] = RefKind::table(&[1, 2]);
+
+    const { assert!(NUMBER.is_ref()) };
+    assert_eq!(TABLE, [Some(RefKind::Ref(&1)), Some(RefKind::Ref(&2))]);
+}
+
+#[test]
+fn index() {
+    use core::ops::Index;
+
+    fn first<T: Index<usize, Output = i32>>(items: T) -> i32 {
+        items[0]
+    }
+
+    let mut numbers = [1, 2, 3];
+    assert_eq!(RefKind::from(&numbers[..])[2], 3);
+    assert_eq!(first(RefKind::from(&mut numbers)), 1);
+}
+
+#[test]
+fn extend() {
+    let mut items = vec![1];
+    let mut kind = RefKind::from(&mut items);
+    kind.extend([2, 3]);
+    assert_eq!(items, [1, 2, 3]);
+
+    let result = std::panic::catch_unwind(|| RefKind::from(&vec![1]).extend([2]));
+    assert!(result.is_err());
+}
+
+#[test]
+fn by_address_ignores_value() {
+    use ref_kind::ByAddress;
+
+    let mut numbers = [7, 7];
+    let [first, second] = numbers.each_mut();
+    let first = ByAddress(RefKind::from(first));
+    let second = ByAddress(RefKind::from(&*second));
+
+    assert_ne!(first, second);
+    assert_eq!(first, ByAddress(RefKind::from(&*first)));
+    assert!(first < second);
+    assert_eq!(*first, 7);
+}
+
+#[test]
+fn try_into_mut() {
+    let mut number = 42;
+    let unique = RefKind::from(&mut number).try_into_mut().unwrap();
+    *unique += 1;
+    assert_eq!(
+        RefKind::from(&number).try_into_mut(),
+        Err(RefKind::Ref(&43))
+    );
+}
+
+#[test]
+#[cfg(feature = "maybe-uninit")]
+fn write_maybe_uninit() {
+    use core::mem::MaybeUninit;
+
+    let storage = MaybeUninit::<u32>::uninit();
+    let kind = RefKind::from(&storage);
+    let (kind, value) = kind.write(42).unwrap_err();
+    assert!(kind.is_ref());
+    assert_eq!(value, 42);
+
+    let mut storage = [MaybeUninit::uninit(), MaybeUninit::uninit()];
+    for (index, item) in storage.iter_mut().enumerate() {
+        RefKind::from(item).write(index).unwrap();
+    }
+    // SAFETY: every element was initialized above
+    let numbers = unsafe { RefKind::from(&storage[1]).assume_init_kind() };
+    assert_eq!(numbers, RefKind::from(&1));
+}
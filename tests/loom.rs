@@ -0,0 +1,185 @@
+//! Models of the thread-safe containers checked by `loom` crate.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --features sync --test loom --release`.
+
+#![cfg(all(loom, feature = "sync"))]
+
+use loom::{sync::Arc, thread};
+
+use ref_kind::{
+    sync::{AtomicRefKind, SyncMany, SyncTypeMap},
+    MoveError, RefKind,
+};
+
+fn leak<T>(value: T) -> &'static mut T {
+    Box::leak(Box::new(value))
+}
+
+#[test]
+fn atomic_mut_race() {
+    loom::model(|| {
+        let atomic = Arc::new(AtomicRefKind::from(leak(0)));
+
+        let other = Arc::clone(&atomic);
+        let handle = thread::spawn(move || other.try_move_mut().is_ok());
+        let won = atomic.try_move_mut().is_ok();
+        let other_won = handle.join().unwrap();
+
+        assert!(won ^ other_won);
+        assert_eq!(atomic.try_move_ref(), Err(MoveError::BorrowedMutably));
+    });
+}
+
+#[test]
+fn atomic_ref_mut_race() {
+    loom::model(|| {
+        let atomic = Arc::new(AtomicRefKind::from(leak(42)));
+
+        let other = Arc::clone(&atomic);
+        let handle = thread::spawn(move || other.try_move_ref().copied());
+        let unique = atomic.try_move_mut();
+        let shared = handle.join().unwrap();
+
+        match (unique, shared) {
+            (Ok(unique), Err(error)) => {
+                assert_eq!(*unique, 42);
+                assert_eq!(error, MoveError::BorrowedMutably);
+            }
+            (Err(error), Ok(shared)) => {
+                assert_eq!(shared, 42);
+                assert_eq!(error, MoveError::BorrowedImmutably);
+            }
+            (unique, shared) => panic!("unexpected outcome: {unique:?}, {shared:?}"),
+        }
+    });
+}
+
+#[test]
+fn atomic_shared_race() {
+    loom::model(|| {
+        let atomic = Arc::new(AtomicRefKind::from(leak(42)));
+
+        let other = Arc::clone(&atomic);
+        let handle = thread::spawn(move || other.try_move_ref().map(|shared| shared as *const _));
+        let shared = atomic.try_move_ref().map(|shared| shared as *const _);
+        let other_shared = handle.join().unwrap();
+
+        // Both threads observe the same immutable reference, whichever of them replaced the mutable one.
+        assert!(shared.is_ok());
+        assert_eq!(shared, other_shared);
+        assert_eq!(atomic.try_move_mut(), Err(MoveError::BorrowedImmutably));
+    });
+}
+
+#[test]
+fn atomic_give_back() {
+    loom::model(|| {
+        let atomic = Arc::new(AtomicRefKind::from(leak(0)));
+
+        let other = Arc::clone(&atomic);
+        let handle = thread::spawn(move || {
+            if let Ok(unique) = other.try_move_mut() {
+                *unique += 1;
+                other.give_back(unique).unwrap();
+            }
+        });
+        let shared = atomic.try_move_ref().copied();
+        handle.join().unwrap();
+
+        // Either the reference was shared before the move, or it was moved and given back in time.
+        match shared {
+            Ok(shared) => assert!(shared <= 1),
+            Err(error) => assert_eq!(error, MoveError::BorrowedMutably),
+        }
+        let atomic = Arc::try_unwrap(atomic).unwrap();
+        assert!(atomic.into_inner().is_some());
+    });
+}
+
+#[test]
+fn atomic_give_back_while_taking() {
+    loom::model(|| {
+        let atomic = Arc::new(AtomicRefKind::from(leak(0)));
+
+        let other = Arc::clone(&atomic);
+        let handle = thread::spawn(move || other.try_move_mut().map(|unique| *unique));
+        // Giving back succeeds only after the other thread has moved the reference out.
+        let given = atomic.give_back(leak(1)).is_ok();
+        let taken = handle.join().unwrap();
+
+        assert_eq!(taken, Ok(0));
+        let atomic = Arc::try_unwrap(atomic).unwrap();
+        assert_eq!(atomic.into_inner().is_some(), given);
+    });
+}
+
+#[test]
+fn sync_many_disjoint_and_same_keys() {
+    loom::model(|| {
+        let numbers = leak([0, 1]);
+        let many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+        let many = Arc::new(SyncMany::new(many));
+
+        let other = Arc::clone(&many);
+        let handle = thread::spawn(move || {
+            let first = other.try_move_mut(0).map(|number| number.is_some());
+            let second = other.try_move_mut(1).map(|number| number.is_some());
+            (first, second)
+        });
+        let second = many.try_move_mut(1).map(|number| number.is_some());
+        let (other_first, other_second) = handle.join().unwrap();
+
+        assert_eq!(other_first, Ok(true));
+        assert!(second.is_ok() ^ other_second.is_ok());
+    });
+}
+
+#[test]
+fn sync_type_map_bundle_is_atomic() {
+    struct A;
+    struct B;
+
+    loom::model(|| {
+        let mut map = SyncTypeMap::new();
+        map.insert_bundle::<(A, B)>((RefKind::from(leak(A)), RefKind::from(leak(B))));
+        let map = Arc::new(map);
+
+        let other = Arc::clone(&map);
+        let handle = thread::spawn(move || other.try_claim_bundle::<(A, B)>().is_ok());
+        // Single move of `A` never observes the bundle claimed partially.
+        let single = map.try_move_mut::<A>().is_ok();
+        let bundle = handle.join().unwrap();
+
+        assert!(single ^ bundle);
+        assert_eq!(map.try_move_mut::<B>().is_ok(), single);
+    });
+}
+
+#[test]
+fn sync_type_map_overlapping_bundles() {
+    struct A;
+    struct B;
+    struct C;
+
+    loom::model(|| {
+        let mut map = SyncTypeMap::new();
+        map.insert(RefKind::from(leak(A)));
+        map.insert(RefKind::from(leak(B)));
+        map.insert(RefKind::from(leak(C)));
+        let map = Arc::new(map);
+
+        let other = Arc::clone(&map);
+        let handle = thread::spawn(move || other.try_claim_bundle::<(A, B)>().is_ok());
+        let won = map.try_claim_bundle::<(B, C)>().is_ok();
+        let other_won = handle.join().unwrap();
+
+        // Type `B` is claimed by at most one bundle, and the loser moves nothing out.
+        assert!(!(won && other_won));
+        if !won {
+            assert!(map.try_move_mut::<C>().is_ok());
+        }
+        if !other_won {
+            assert!(map.try_move_mut::<A>().is_ok());
+        }
+    });
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashMap;
+
+use ref_kind::{Many, MoveError, RefKind};
+
+#[test]
+fn nested_hash_map() {
+    let mut numbers = [1, 2, 3];
+    let inner: HashMap<_, _> = numbers
+        .iter_mut()
+        .enumerate()
+        .map(|(i, n)| (i, Some(RefKind::from(n))))
+        .collect();
+    let mut outer = HashMap::from([(0, inner)]);
+
+    let one = outer.move_mut(0).unwrap().unwrap();
+    assert_eq!(*one, 1);
+    assert_eq!(outer.try_move_ref(0), Err(MoveError::BorrowedMutably));
+
+    let missing = outer.move_mut(1);
+    assert!(missing.is_none());
+}
+
+#[test]
+fn aliases_share_availability() {
+    use ref_kind::Aliased;
+
+    let mut first = 1;
+    let many = HashMap::from([("first", Some(RefKind::from(&mut first)))]);
+    let mut many = Aliased::new(many);
+    many.alias("old", "first");
+    many.alias("older", "old");
+    assert_eq!(many.resolve("older"), "first");
+
+    assert_eq!(many.try_move_ref("older"), Ok(Some(&1)));
+    assert_eq!(many.try_move_mut("old"), Err(MoveError::BorrowedImmutably));
+
+    assert_eq!(many.unalias(&"old"), Some("first"));
+    assert_eq!(many.try_move_ref("old"), Ok(None));
+}
+
+#[test]
+fn chained_aliases_are_repointed() {
+    use ref_kind::Aliased;
+
+    let mut first = 1;
+    let many = HashMap::from([("c", Some(RefKind::from(&mut first)))]);
+    let mut many = Aliased::new(many);
+    many.alias("a", "b");
+    many.alias("b", "c");
+    assert_eq!(many.resolve("a"), "c");
+
+    assert_eq!(many.try_move_mut("a"), Ok(Some(&mut 1)));
+    assert_eq!(many.try_move_ref("b"), Err(MoveError::BorrowedMutably));
+
+    // Aliasing the target back to its alias does not create a cycle
+    assert_eq!(many.alias("c", "a"), None);
+    assert_eq!(many.resolve("c"), "c");
+    assert_eq!(many.resolve("a"), "c");
+}
+
+#[test]
+#[cfg(feature = "panic-payload")]
+fn panic_payload_exposes_key() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use ref_kind::MovePanic;
+
+    let mut volume = 50;
+    let mut many = HashMap::from([("audio.volume", Some(RefKind::from(&mut volume)))]);
+    let _ = many.move_mut("audio.volume");
+
+    let payload = panic::catch_unwind(AssertUnwindSafe(|| many.move_ref_keyed("audio.volume")));
+    let payload = payload.unwrap_err();
+    let payload = payload.downcast_ref::<MovePanic>().unwrap();
+    assert_eq!(payload.error, MoveError::BorrowedMutably);
+    assert_eq!(payload.key_debug.as_deref(), Some(r#""audio.volume""#));
+
+    // Key is not reported by methods which do not require it to implement `Debug`
+    let payload = panic::catch_unwind(AssertUnwindSafe(|| many.move_ref("audio.volume")));
+    let payload = payload.unwrap_err();
+    let payload = payload.downcast_ref::<MovePanic>().unwrap();
+    assert_eq!(payload.key_debug, None);
+}
+
+#[test]
+#[cfg(not(feature = "panic-payload"))]
+#[should_panic = "failed to move reference by key 1: "]
+fn panic_message_contains_key() {
+    let mut numbers = [1, 2];
+    let mut many = numbers.each_mut().map(|i| Some(RefKind::from(i)));
+    let _ = many.move_mut(1);
+    many.move_mut_keyed(1);
+}
+
+#[test]
+fn allocated_bytes_of_vec_backed_collections() {
+    use ref_kind::{ManyCursor, SortedMany};
+
+    let mut numbers = [1, 2, 3];
+    let sorted: SortedMany<_, _> = numbers
+        .iter_mut()
+        .enumerate()
+        .map(|(key, number)| (key, Some(RefKind::from(number))))
+        .collect();
+    assert!(sorted.allocated_bytes() >= 3 * size_of::<(usize, Option<RefKind<i32>>)>());
+
+    let mut numbers = [1, 2, 3];
+    let mut cursor = ManyCursor::new(&mut numbers);
+    cursor.advance();
+    assert_eq!(cursor.allocated_bytes(), 0);
+    cursor.next();
+    assert!(cursor.allocated_bytes() >= size_of::<(usize, &mut [i32])>());
+}
+
+#[test]
+fn cursor_moves_out_of_skipped_runs() {
+    use ref_kind::{ManyCursor, MoveError};
+
+    let mut numbers = [0, 1, 2, 3, 4, 5];
+    let mut cursor = ManyCursor::new(&mut numbers);
+    cursor.advance();
+    cursor.advance();
+    cursor.advance();
+    assert_eq!(cursor.next(), Some(&mut 3));
+    cursor.advance();
+
+    assert_eq!(cursor.try_move_ref(1), Ok(Some(&1)));
+    assert_eq!(cursor.try_move_ref(1), Ok(Some(&1)));
+    assert_eq!(cursor.try_move_mut(1), Err(MoveError::BorrowedImmutably));
+    assert_eq!(cursor.try_move_mut(3), Err(MoveError::BorrowedMutably));
+    assert_eq!(cursor.try_move_mut(2), Ok(Some(&mut 2)));
+    assert_eq!(cursor.try_move_ref(2), Err(MoveError::BorrowedMutably));
+    assert_eq!(cursor.try_move_mut(0), Ok(Some(&mut 0)));
+    assert_eq!(cursor.try_move_mut(4), Ok(Some(&mut 4)));
+    assert_eq!(cursor.try_move_mut(5), Ok(None));
+
+    assert_eq!(cursor.remaining(), 1);
+    assert_eq!(cursor.next(), Some(&mut 5));
+    assert_eq!(cursor.next(), None);
+    assert_eq!(cursor.position(), 6);
+}
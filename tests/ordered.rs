@@ -0,0 +1,73 @@
+#![cfg(feature = "std")]
+
+use std::collections::{BTreeMap, VecDeque};
+
+use ref_kind::{DequeMany, Many, MoveError, OrderedMany, RefKind};
+
+#[test]
+fn deque_fifo_order() {
+    let mut jobs = [1, 2, 3, 4];
+    let mut queue: VecDeque<_> = jobs.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    queue.try_move_ref(0).unwrap();
+
+    assert_eq!(queue.move_first_mut(), Some((1, &mut 2)));
+    assert_eq!(queue.move_last_mut(), Some((3, &mut 4)));
+    assert_eq!(queue.move_first_mut(), Some((2, &mut 3)));
+    assert_eq!(queue.move_first_mut(), None);
+
+    assert_eq!(queue.pop_move_first(), Some((0, RefKind::from(&1))));
+    // Front entry was moved out, so nothing is removed and keys are not shifted
+    assert_eq!(queue.pop_move_first(), None);
+    assert_eq!(queue.len(), 3);
+}
+
+#[test]
+fn deque_pops_only_front() {
+    let mut jobs = [1, 2, 3];
+    let mut queue: VecDeque<_> = jobs.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    queue.try_move_mut(1).unwrap();
+
+    assert_eq!(queue.pop_front_kind(), Some(RefKind::from(&mut 1)));
+    assert_eq!(queue.pop_front_kind(), None);
+    assert_eq!(queue.len(), 2);
+    // Key of the last entry was shifted by the popped front entry only
+    assert_eq!(queue.try_move_mut(1), Ok(Some(&mut 3)));
+}
+
+#[test]
+fn deque_front_back_are_strict() {
+    let mut jobs = [1, 2, 3];
+    let mut queue: VecDeque<_> = jobs.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    queue.try_move_mut(0).unwrap();
+
+    // Busy front entry is reported instead of being skipped
+    assert_eq!(queue.move_front_mut(), Err(MoveError::BorrowedMutably));
+    assert_eq!(queue.move_back_mut(), Ok(Some(&mut 3)));
+    assert_eq!(queue.move_back_mut(), Err(MoveError::BorrowedMutably));
+
+    queue.rotate_right(2);
+    assert_eq!(queue.move_front_mut(), Ok(Some(&mut 2)));
+
+    queue.clear();
+    assert_eq!(queue.move_front_mut(), Ok(None));
+    assert_eq!(queue.move_back_mut(), Ok(None));
+}
+
+#[test]
+fn move_range_skips_unavailable() {
+    let mut samples = [1, 2, 3, 4, 5];
+    let mut btree: BTreeMap<_, _> = samples
+        .iter_mut()
+        .enumerate()
+        .map(|(i, n)| (i * 10, Some(RefKind::from(n))))
+        .collect();
+    btree.try_move_ref(20).unwrap();
+
+    assert_eq!(btree.move_range_mut(10..=30), [(10, &mut 2), (30, &mut 4)]);
+    assert_eq!(btree.move_range_mut(..), [(0, &mut 1), (40, &mut 5)]);
+
+    let mut samples = [1, 2, 3];
+    let mut queue: VecDeque<_> = samples.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    assert_eq!(queue.move_range_mut(1..), [(1, &mut 2), (2, &mut 3)]);
+    assert_eq!(queue.move_range_mut(5..), []);
+}
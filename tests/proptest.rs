@@ -0,0 +1,26 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use ref_kind::proptest::{apply, availabilities, ops, Availability, Model};
+
+proptest! {
+    #[test]
+    fn model_agrees_with_collection(states in availabilities(0..8), ops in ops(0..10usize, 0..32)) {
+        let mut owner = vec![0; states.len()];
+        let mut many = apply(&mut owner, &states);
+        let mut model = Model::new(states);
+
+        for op in ops {
+            let expected = model.step(op);
+            let actual = op.run(&mut many);
+            prop_assert_eq!(actual, expected);
+        }
+    }
+}
+
+#[test]
+#[should_panic = "each element of the owner must have its own availability state"]
+fn apply_without_state_for_every_element() {
+    let mut owner = [0, 1];
+    apply(&mut owner, &[Availability::Ref]);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "alloc")]
+
+use ref_kind::{DedupMany, RefKind, SortMany};
+
+#[test]
+fn stable_sort_remap() {
+    let mut items = [(2, 'a'), (1, 'b'), (2, 'c'), (0, 'd')];
+    let (first, rest) = items.split_at_mut(1);
+    let mut many = vec![Some(RefKind::from(&first[0]))];
+    many.extend(rest.iter_mut().map(|i| Some(RefKind::from(i))));
+
+    let remap = many.sort_by_pointee(|a, b| a.0.cmp(&b.0)).unwrap();
+    assert_eq!(remap, [2, 1, 3, 0]);
+
+    let order: Vec<_> = many.iter().flatten().map(|kind| kind.get_ref().1).collect();
+    assert_eq!(order, ['d', 'b', 'a', 'c']);
+    assert!(many[2].as_ref().unwrap().is_ref());
+}
+
+#[test]
+fn dedup_by_address() {
+    let values = [1, 1];
+    let mut many = vec![
+        Some(RefKind::from(&values[0])),
+        Some(RefKind::from(&values[1])),
+        Some(RefKind::from(&values[1])),
+        None,
+        Some(RefKind::from(&values[1])),
+    ];
+
+    let removed = many.dedup_by_pointee(|a, b| std::ptr::eq(a, b));
+    assert_eq!(removed, [2]);
+    assert_eq!(many.len(), 4);
+    assert!(many[2].is_none());
+}
+
+#[test]
+fn sorted_many_last_key_wins() {
+    use ref_kind::{Many, SortedMany};
+
+    let (mut old, mut new, mut other) = (1, 2, 3);
+    let mut many: SortedMany<_, _> = [
+        (5, Some(RefKind::from(&mut old))),
+        (1, Some(RefKind::from(&mut other))),
+        (5, Some(RefKind::from(&mut new))),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(many.len(), 2);
+
+    assert!(many.remove(&1).is_some());
+    assert!(many.insert(3, None).is_none());
+    assert_eq!(many.keys().copied().collect::<Vec<_>>(), [3, 5]);
+    assert_eq!(many.try_move_mut(5), Ok(Some(&mut 2)));
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "alloc")]
+
+use ref_kind::SparseSet;
+
+#[test]
+fn insert_remove() {
+    let mut set = SparseSet::new();
+    assert_eq!(set.insert(5, 'a'), None);
+    assert_eq!(set.insert(1, 'b'), None);
+    assert_eq!(set.insert(9, 'c'), None);
+    assert_eq!(set.insert(1, 'd'), Some('b'));
+    assert_eq!(set.len(), 3);
+
+    assert_eq!(set.remove(5), Some('a'));
+    assert_eq!(set.remove(5), None);
+    assert_eq!(set.keys(), [9, 1]);
+    assert_eq!(set.get(9), Some(&'c'));
+    assert_eq!(set.get(1), Some(&'d'));
+    assert!(!set.contains(5));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn rearm_foreign_reference() {
+    use ref_kind::{Many, Rearmable, RefKind};
+
+    let mut numbers = [1, 2];
+    let mut foreign = 3;
+    let set = numbers
+        .iter_mut()
+        .enumerate()
+        .map(|(i, n)| (i, Some(RefKind::from(n))))
+        .collect();
+    let mut many = Rearmable::new(set);
+
+    let first = many.try_move_mut(0).unwrap().unwrap();
+    assert_eq!(many.give_back(&mut foreign), Err(&mut 3));
+    assert_eq!(many.give_back(first), Ok(0));
+
+    let first = many.try_move_mut(0).unwrap().unwrap();
+    assert_eq!(many.give_back(first), Ok(0));
+    assert_eq!(many.get_ref().len(), 2);
+}
+
+#[test]
+fn check_in_mismatched() {
+    use ref_kind::{Many, RefKind, RefKindSparseSet};
+
+    let mut numbers = [1, 2];
+    let mut set: RefKindSparseSet<_> = numbers
+        .iter_mut()
+        .enumerate()
+        .map(|(i, n)| (i, Some(RefKind::from(n))))
+        .collect();
+
+    let (first, first_token) = set.checkout_mut(0).unwrap().unwrap();
+    let (second, second_token) = set.checkout_mut(1).unwrap().unwrap();
+    let (first_token, second) = set.check_in(first_token, second).unwrap_err();
+    let (second_token, first) = set.check_in(second_token, first).unwrap_err();
+    assert_eq!(set.check_in(first_token, first), Ok(0));
+    assert_eq!(set.check_in(second_token, second), Ok(1));
+    assert_eq!(set.try_move_ref(0), Ok(Some(&1)));
+    assert_eq!(set.checkout_mut(5).map(|c| c.is_none()), Ok(true));
+}
+
+#[test]
+fn check_in_foreign_token() {
+    use ref_kind::{Many, MoveError, RefKind, RefKindSparseSet};
+
+    let mut first = [1];
+    let mut second = [2];
+    let mut first: RefKindSparseSet<_> = first
+        .iter_mut()
+        .map(|n| (0, Some(RefKind::from(n))))
+        .collect();
+    let mut second: RefKindSparseSet<_> = second
+        .iter_mut()
+        .map(|n| (0, Some(RefKind::from(n))))
+        .collect();
+
+    let (number, token) = first.checkout_mut(0).unwrap().unwrap();
+    let _ = second.try_move_mut(0).unwrap();
+
+    // Token of the first set does not restore the entry of the second set with the same key
+    let (token, number) = second.check_in(token, number).unwrap_err();
+    assert_eq!(second.try_move_ref(0), Err(MoveError::BorrowedMutably));
+    assert_eq!(first.check_in(token, number), Ok(0));
+    assert_eq!(first.try_move_ref(0), Ok(Some(&1)));
+}
+
+#[test]
+#[cfg(all(debug_assertions, feature = "std"))]
+#[should_panic = "leaked without check-in"]
+fn claim_token_leaked() {
+    use ref_kind::{RefKind, RefKindSparseSet};
+
+    let mut number = 1;
+    let mut set = RefKindSparseSet::new();
+    set.insert(0, Some(RefKind::from(&mut number)));
+    let (_, token) = set.checkout_mut(0).unwrap().unwrap();
+    drop(token);
+}
+
+#[test]
+fn split_off_with_panicking_predicate() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let mut set: SparseSet<_> = [(4, 'a'), (1, 'b'), (7, 'c')].into_iter().collect();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        set.split_off_with(|key, _| match key {
+            7 => panic!("predicate panicked"),
+            key => key == 1,
+        })
+    }));
+    assert!(result.is_err());
+
+    assert_eq!(set.keys(), [4, 1, 7]);
+    assert_eq!(set.get(1), Some(&'b'));
+    assert_eq!(set.get(7), Some(&'c'));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn debug_validate_after_changes() {
+    use ref_kind::{Many, Rearmable, RefKind, RefKindSparseSet};
+
+    let mut numbers = [1, 2, 3, 4];
+    let mut set: RefKindSparseSet<_> = numbers
+        .iter_mut()
+        .enumerate()
+        .map(|(i, n)| (i * 3, Some(RefKind::from(n))))
+        .collect();
+    set.remove(3);
+    let _ = set.split_off_with(|key, _| key == 9);
+    set.debug_validate();
+
+    let mut many = Rearmable::new(set);
+    let number = many.try_move_mut(6).unwrap().unwrap();
+    many.debug_validate();
+    assert_eq!(many.give_back(number), Ok(6));
+    many.debug_validate();
+}
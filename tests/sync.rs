@@ -0,0 +1,185 @@
+#![cfg(all(feature = "sync", not(loom)))]
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use ref_kind::{
+    access::{AccessSet, ClaimError},
+    sync::{run_parallel, scatter, AtomicRefKind, ScatterError, SyncMany, SyncTypeMap},
+    Many, MoveError, MoveResult, RefKind,
+};
+
+#[test]
+fn atomic_race_mut() {
+    let mut number = 42;
+    let atomic = AtomicRefKind::from(&mut number);
+    let winners = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                if let Ok(number) = atomic.try_move_mut() {
+                    *number += 1;
+                    winners.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    assert_eq!(winners.into_inner(), 1);
+    assert_eq!(atomic.try_move_ref(), Err(MoveError::BorrowedMutably));
+    assert_eq!(number, 43);
+}
+
+#[test]
+fn atomic_race_ref() {
+    let mut number = 42;
+    let atomic = AtomicRefKind::from(&mut number);
+
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| assert_eq!(atomic.try_move_ref(), Ok(&42)));
+        }
+    });
+    assert_eq!(atomic.try_move_mut(), Err(MoveError::BorrowedImmutably));
+    assert!(atomic.into_inner().unwrap().is_ref());
+}
+
+#[test]
+fn sync_many_poisoned() {
+    struct Panicking;
+
+    impl<'a> Many<'a, ()> for Panicking {
+        type Ref = ();
+
+        fn try_move_ref(&mut self, _: ()) -> MoveResult<Self::Ref> {
+            panic!("move out of panicking collection")
+        }
+
+        type Mut = ();
+
+        fn try_move_mut(&mut self, _: ()) -> MoveResult<Self::Mut> {
+            Ok(())
+        }
+    }
+
+    let many = SyncMany::new(Panicking);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| many.try_move_ref(())));
+    assert!(result.is_err());
+    assert!(many.is_poisoned());
+    assert_eq!(many.try_move_mut(()), Err(MoveError::Poisoned));
+
+    many.clear_poison();
+    assert_eq!(many.try_move_mut(()), Ok(()));
+}
+
+#[test]
+fn sync_many_scope_poisoned() {
+    let mut numbers = [1, 2];
+    let many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    let many = SyncMany::new(many);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        many.scope(|many| {
+            let _ = many.try_move_mut(0);
+            panic!("panic with half-claimed collection")
+        })
+    }));
+    assert!(result.is_err());
+    assert!(many.is_poisoned());
+    assert_eq!(many.try_move_mut(1), Err(MoveError::Poisoned));
+    assert_eq!(many.scope(|_| ()), Err(MoveError::Poisoned));
+
+    many.clear_poison();
+    assert_eq!(many.try_move_mut(1), Ok(Some(&mut 2)));
+}
+
+#[test]
+fn scatter_overlapping_groups() {
+    let mut numbers = [1, 2, 3];
+    let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+
+    let result = scatter(&mut many, [vec![0, 1], vec![1, 2]], |_, _| ());
+    assert_eq!(result, Err(ScatterError::DuplicateKey(1)));
+    assert!(many
+        .iter()
+        .all(|item| item.as_ref().is_some_and(RefKind::is_mut)));
+}
+
+#[test]
+fn scatter_unavailable_keys() {
+    let mut numbers = [1, 2, 3];
+    let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+    many.try_move_ref(2).unwrap();
+
+    let result = scatter(&mut many, [vec![0], vec![1, 2]], |_, _| ());
+    let error = ClaimError {
+        key: 2,
+        error: MoveError::BorrowedImmutably,
+    };
+    assert_eq!(result, Err(ScatterError::Unavailable(error)));
+    let result = scatter(&mut many, [vec![0], vec![3]], |_, _| ());
+    let error = ClaimError {
+        key: 3,
+        error: MoveError::NotFound,
+    };
+    assert_eq!(result, Err(ScatterError::Unavailable(error)));
+
+    // References of the earlier groups were not moved out by the failed scatters
+    assert!(many[..2]
+        .iter()
+        .all(|item| item.as_ref().is_some_and(RefKind::is_mut)));
+}
+
+#[test]
+fn bundle_rollback() {
+    let (mut position, mut velocity) = (1, 2.0);
+
+    let mut map = SyncTypeMap::new();
+    map.insert(RefKind::from(&mut position));
+    map.insert(RefKind::from(&mut velocity));
+
+    let duplicate = map.try_claim_bundle::<(i32, f64, i32)>();
+    assert_eq!(duplicate.err(), Some(MoveError::BorrowedMutably));
+    let missing = map.try_claim_bundle::<(i32, f64, u8)>();
+    assert!(matches!(missing, Ok(None)));
+
+    let (position, velocity) = map.try_claim_bundle::<(i32, f64)>().unwrap().unwrap();
+    *position += 1;
+    *velocity *= 2.0;
+    assert_eq!(map.try_move_mut::<i32>(), Err(MoveError::BorrowedMutably));
+}
+
+#[test]
+fn bundle_insert_and_failed_claim() {
+    let (mut position, mut velocity, mass) = (1, 2.0, 3_u8);
+
+    let mut map = SyncTypeMap::new();
+    let previous = map.insert_bundle::<(i32, f64, u8)>((
+        RefKind::from(&mut position),
+        RefKind::from(&mut velocity),
+        RefKind::from(&mass),
+    ));
+    assert!(matches!(previous, (None, None, None)));
+
+    // Failed claim does not move out references of the other types
+    let error = map.try_claim_bundle::<(i32, f64, u8)>();
+    assert_eq!(error.err(), Some(MoveError::BorrowedImmutably));
+    assert_eq!(map.try_move_mut::<i32>(), Ok(Some(&mut 1)));
+    assert_eq!(map.try_move_mut::<f64>(), Ok(Some(&mut 2.0)));
+}
+
+#[test]
+fn run_parallel_conflicting_accesses() {
+    let mut numbers = [1, 2, 3];
+    let mut many: Vec<_> = numbers.iter_mut().map(|i| Some(RefKind::from(i))).collect();
+
+    let accesses = [AccessSet::new().read(0), AccessSet::new().write(0).write(1)];
+    let error = run_parallel(&mut many, &accesses, |_, _| ()).unwrap_err();
+    assert_eq!((error.key, error.error), (0, MoveError::BorrowedImmutably));
+    assert!(many
+        .iter()
+        .all(|item| item.as_ref().is_some_and(RefKind::is_mut)));
+}